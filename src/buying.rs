@@ -0,0 +1,144 @@
+//! Buying formats of listings.
+
+use core::{fmt, str::FromStr};
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::vocabulary::{self, Vocabulary};
+
+static BIDS_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d+").unwrap());
+
+/// How a listing is sold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuyingFormat {
+    Auction,
+    /// A fixed price.
+    BuyItNow,
+    /// A fixed price, only shown as open to offers.
+    BestOffer,
+    /// An ad, the sale happens outside of eBay.
+    Classified,
+}
+
+/// The buying format of a listing along with its auction and offer details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Buying {
+    /// `None` when the listing shows no hint of its format.
+    pub format: Option<BuyingFormat>,
+    /// The number of bids of an auction.
+    pub bids: Option<u32>,
+    /// Whether the seller accepts offers.
+    pub accepts_offers: bool,
+}
+
+impl BuyingFormat {
+    pub const ALL: [Self; 4] = [
+        Self::Auction,
+        Self::BuyItNow,
+        Self::BestOffer,
+        Self::Classified,
+    ];
+
+    /// The name of the format, as accepted by [`BuyingFormat::from_str`].
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Auction => "auction",
+            Self::BuyItNow => "buy-it-now",
+            Self::BestOffer => "best-offer",
+            Self::Classified => "classified",
+        }
+    }
+
+    /// The label of the format, as shown in entries.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Auction => "Auction",
+            Self::BuyItNow => "Buy It Now",
+            Self::BestOffer => "Best Offer",
+            Self::Classified => "Classified ad",
+        }
+    }
+}
+
+impl fmt::Display for BuyingFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for BuyingFormat {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.name() == name)
+            .ok_or_else(|| format!("unknown buying format {name:?}"))
+    }
+}
+
+impl Buying {
+    /// Classify a listing from its purchase options, ad label and bid count
+    /// texts.
+    pub fn parse(
+        purchase_options: Option<&str>,
+        ad: Option<&str>,
+        bids: Option<&str>,
+        vocabulary: &Vocabulary,
+    ) -> Self {
+        let options = purchase_options.unwrap_or_default();
+        let ad = ad.unwrap_or_default();
+        let has = |text: &str, phrases: &[&str]| vocabulary::contains(text, phrases);
+        let has_word = |text: &str, words: &[&str]| {
+            words
+                .iter()
+                .any(|word| vocabulary::contains_word(text, word))
+        };
+
+        let bids = bids.and_then(|bids| BIDS_REGEX.find(bids)?.as_str().parse().ok());
+
+        let accepts_offers = has(options, vocabulary.best_offer);
+
+        let format = if has(ad, vocabulary.classified) || has(options, vocabulary.classified) {
+            Some(BuyingFormat::Classified)
+        } else if bids.is_some() || has_word(options, vocabulary.auction) {
+            Some(BuyingFormat::Auction)
+        } else if has(options, vocabulary.buy_it_now) {
+            Some(BuyingFormat::BuyItNow)
+        } else if accepts_offers {
+            Some(BuyingFormat::BestOffer)
+        } else {
+            None
+        };
+
+        Self {
+            format,
+            bids,
+            accepts_offers,
+        }
+    }
+}
+
+impl fmt::Display for Buying {
+    /// Write the badge of the listing, such as `Auction, 3 bids` or
+    /// `Buy It Now, accepts offers`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.format {
+            Some(format) => f.write_str(format.label())?,
+            None => f.write_str("Unknown format")?,
+        }
+
+        match self.bids {
+            Some(1) => f.write_str(", 1 bid")?,
+            Some(bids) => write!(f, ", {bids} bids")?,
+            None => {}
+        }
+
+        if self.accepts_offers && self.format != Some(BuyingFormat::BestOffer) {
+            f.write_str(", accepts offers")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+//! Canonical item conditions.
+
+use core::{fmt, str::FromStr};
+
+use crate::vocabulary::{self, Vocabulary};
+
+/// The condition of an item, the same across sites and categories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Condition {
+    New,
+    /// New, but not in its original packaging.
+    OpenBox,
+    Refurbished,
+    Used,
+    /// Not working, sold for parts.
+    ForParts,
+}
+
+impl Condition {
+    pub const ALL: [Self; 5] = [
+        Self::New,
+        Self::OpenBox,
+        Self::Refurbished,
+        Self::Used,
+        Self::ForParts,
+    ];
+
+    /// Map a condition as shown on the page, such as `Brand New`, `Gebraucht`
+    /// or `Parts Only`, `None` if it is not recognized.
+    pub fn parse(text: &str, vocabulary: &Vocabulary) -> Option<Self> {
+        vocabulary
+            .conditions
+            .iter()
+            .find(|(_, phrases)| vocabulary::contains(text, phrases))
+            .map(|&(condition, _)| condition)
+    }
+
+    /// The name of the condition, as accepted by [`Condition::from_str`].
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::New => "new",
+            Self::OpenBox => "open-box",
+            Self::Refurbished => "refurbished",
+            Self::Used => "used",
+            Self::ForParts => "for-parts",
+        }
+    }
+
+    /// The label of the condition, as shown in entries.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::New => "New",
+            Self::OpenBox => "Open box",
+            Self::Refurbished => "Refurbished",
+            Self::Used => "Used",
+            Self::ForParts => "For parts",
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Condition {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|condition| condition.name() == name)
+            .ok_or_else(|| format!("unknown condition {name:?}"))
+    }
+}
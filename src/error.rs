@@ -0,0 +1,67 @@
+use core::fmt;
+
+/// A fatal, page-level extraction error.
+///
+/// These errors mean the input is not a usable search results page, so no
+/// feed can be produced from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The search box holding the keywords is missing.
+    MissingSearchTitle,
+    /// The `baseUrl` of the search is missing from the page data.
+    MissingSearchUrl,
+    /// The results container is missing.
+    MissingResults,
+    /// A selector or regex of the profile does not compile.
+    InvalidProfile(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSearchTitle => write!(f, "search title not found"),
+            Self::MissingSearchUrl => write!(f, "search URL not found"),
+            Self::MissingResults => write!(f, "results container not found"),
+            Self::InvalidProfile(message) => write!(f, "invalid profile: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// An item-level extraction error, the offending item is skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemError {
+    MissingTitle,
+    MissingLink,
+    /// The link does not point to an item page.
+    InvalidLink(String),
+    MissingPrice,
+}
+
+impl fmt::Display for ItemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingTitle => write!(f, "title not found"),
+            Self::MissingLink => write!(f, "link not found"),
+            Self::InvalidLink(link) => write!(f, "invalid item link {link:?}"),
+            Self::MissingPrice => write!(f, "price not found"),
+        }
+    }
+}
+
+impl std::error::Error for ItemError {}
+
+/// An item that was skipped because of an [`ItemError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedItem {
+    /// The 1-based position of the item on the page.
+    pub position: usize,
+    pub error: ItemError,
+}
+
+impl fmt::Display for SkippedItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "skipped item #{}: {}", self.position, self.error)
+    }
+}
@@ -0,0 +1,185 @@
+//! Download of search pages.
+
+use core::fmt;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use cookie_store::{serde::json, CookieStore};
+use ureq::{Agent, AgentBuilder};
+
+/// The default `User-Agent` header.
+pub const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (+",
+    env!("CARGO_PKG_REPOSITORY"),
+    ")"
+);
+
+/// The longest delay between two attempts, however many retries are made.
+pub const MAX_BACKOFF: Duration = Duration::from_secs(3600);
+
+/// How pages are downloaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchConfig {
+    pub user_agent: String,
+    /// The timeout of each attempt.
+    pub timeout: Duration,
+    /// How many times a failed request is retried.
+    pub retries: u32,
+    /// The delay before the first retry, doubled on each further retry up to
+    /// [`MAX_BACKOFF`].
+    pub backoff: Duration,
+    /// A JSON file to load cookies from and save them to.
+    pub cookie_jar: Option<PathBuf>,
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        Self {
+            user_agent: USER_AGENT.to_owned(),
+            timeout: Duration::from_secs(30),
+            retries: 3,
+            backoff: Duration::from_secs(1),
+            cookie_jar: None,
+        }
+    }
+}
+
+/// A download error.
+#[derive(Debug)]
+pub enum FetchError {
+    /// The server answered with an error status.
+    Status { url: String, status: u16 },
+    /// The server could not be reached.
+    Transport { url: String, message: String },
+    /// The response body could not be read.
+    Body { url: String, error: io::Error },
+    /// The cookie jar could not be loaded or saved.
+    CookieJar { path: PathBuf, message: String },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Status { url, status } => write!(f, "{url} returned status {status}"),
+            Self::Transport { url, message } => write!(f, "cannot reach {url}: {message}"),
+            Self::Body { url, error } => write!(f, "cannot read {url}: {error}"),
+            Self::CookieJar { path, message } => {
+                write!(f, "cookie jar {}: {message}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+/// An HTTP client for search pages.
+///
+/// Compressed responses are decoded, and cookies set by the server are sent
+/// back on later requests, including retries.
+pub struct Fetcher {
+    agent: Agent,
+    retries: u32,
+    backoff: Duration,
+    cookie_jar: Option<PathBuf>,
+}
+
+impl Fetcher {
+    pub fn new(config: &FetchConfig) -> Result<Self, FetchError> {
+        let cookies = match &config.cookie_jar {
+            Some(path) => load_cookies(path)?,
+            None => CookieStore::default(),
+        };
+
+        let agent = AgentBuilder::new()
+            .user_agent(&config.user_agent)
+            .timeout(config.timeout)
+            .cookie_store(cookies)
+            .build();
+
+        Ok(Self {
+            agent,
+            retries: config.retries,
+            backoff: config.backoff,
+            cookie_jar: config.cookie_jar.clone(),
+        })
+    }
+
+    /// Download `url`, retrying on transport errors and on server errors.
+    pub fn get(&self, url: &str) -> Result<String, FetchError> {
+        let mut attempt = 0;
+
+        loop {
+            let error = match self.agent.get(url).call() {
+                Ok(response) => {
+                    return response.into_string().map_err(|error| FetchError::Body {
+                        url: url.to_owned(),
+                        error,
+                    })
+                }
+                Err(ureq::Error::Status(status, _)) => FetchError::Status {
+                    url: url.to_owned(),
+                    status,
+                },
+                Err(ureq::Error::Transport(transport)) => FetchError::Transport {
+                    url: url.to_owned(),
+                    message: transport.to_string(),
+                },
+            };
+
+            if attempt >= self.retries || !is_transient(&error) {
+                return Err(error);
+            }
+
+            let delay = self.backoff.checked_mul(2u32.saturating_pow(attempt));
+            thread::sleep(delay.map_or(MAX_BACKOFF, |delay| delay.min(MAX_BACKOFF)));
+            attempt += 1;
+        }
+    }
+
+    /// Save the cookies to the cookie jar, if any.
+    pub fn save_cookies(&self) -> Result<(), FetchError> {
+        let Some(path) = &self.cookie_jar else {
+            return Ok(());
+        };
+
+        let jar_error = |message: String| FetchError::CookieJar {
+            path: path.clone(),
+            message,
+        };
+
+        let mut file = fs::File::create(path).map_err(|error| jar_error(error.to_string()))?;
+        json::save(&self.agent.cookie_store(), &mut file)
+            .map_err(|error| jar_error(error.to_string()))
+    }
+}
+
+fn load_cookies(path: &Path) -> Result<CookieStore, FetchError> {
+    let jar_error = |message: String| FetchError::CookieJar {
+        path: path.to_owned(),
+        message,
+    };
+
+    match fs::File::open(path) {
+        Ok(file) => {
+            json::load(io::BufReader::new(file)).map_err(|error| jar_error(error.to_string()))
+        }
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(CookieStore::default()),
+        Err(error) => Err(jar_error(error.to_string())),
+    }
+}
+
+/// Whether retrying might fix the error.
+fn is_transient(error: &FetchError) -> bool {
+    match error {
+        FetchError::Status { status, .. } => *status == 429 || *status >= 500,
+        FetchError::Transport { .. } => true,
+        FetchError::Body { .. } | FetchError::CookieJar { .. } => false,
+    }
+}
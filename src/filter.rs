@@ -0,0 +1,415 @@
+//! Include and exclude rules applied to the listings before building entries.
+//!
+//! A filter is read from TOML, every rule is optional:
+//!
+//! ```toml
+//! max_total = "500 EUR"
+//! min_price = 20
+//! conditions = ["new", "open-box"]
+//! exclude_keywords = ["broken", "read"]
+//! blocked_sellers = ["gadgetshop"]
+//! rules = ["bids < 5 or time_left > 1d"]
+//! ```
+
+use core::{fmt, str::FromStr};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer};
+
+use crate::{
+    price, profile, rule::Outcome, vocabulary::contains_word, BuyingFormat, Condition, Money, Rule,
+    SearchItem,
+};
+
+static LIMIT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(\d+)(?:\.(\d{1,2}))?\s*(.*?)\s*$").unwrap());
+
+/// Rules a listing must satisfy to be kept.
+///
+/// Listings failing a price or total bound because the amount is unknown are
+/// dropped, as are listings with an unknown condition or format when only
+/// some conditions or formats are allowed.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Filter {
+    #[serde(deserialize_with = "limit")]
+    pub min_price: Option<Limit>,
+    #[serde(deserialize_with = "limit")]
+    pub max_price: Option<Limit>,
+    /// The lowest price with shipping.
+    #[serde(deserialize_with = "limit")]
+    pub min_total: Option<Limit>,
+    /// The highest price with shipping.
+    #[serde(deserialize_with = "limit")]
+    pub max_total: Option<Limit>,
+    /// The allowed conditions, any if empty.
+    #[serde(deserialize_with = "parsed_list")]
+    pub conditions: Vec<Condition>,
+    /// Words the title must all contain.
+    pub keywords: Vec<String>,
+    /// Words the title must not contain.
+    pub exclude_keywords: Vec<String>,
+    /// Regexes the title must all match.
+    #[serde(deserialize_with = "parsed_list")]
+    pub title_regexes: Vec<Regex>,
+    /// Regexes the title must not match.
+    #[serde(deserialize_with = "parsed_list")]
+    pub exclude_title_regexes: Vec<Regex>,
+    /// Sellers whose listings are dropped.
+    pub blocked_sellers: Vec<String>,
+    /// The allowed buying formats, any if empty.
+    #[serde(deserialize_with = "parsed_list")]
+    pub formats: Vec<BuyingFormat>,
+    /// Rules the listing must all match.
+    #[serde(deserialize_with = "parsed_list")]
+    pub rules: Vec<Rule>,
+}
+
+/// A price bound, such as `500` or `12.50 EUR`.
+///
+/// Without a currency the bound applies to any currency, otherwise listings
+/// priced in another currency fail it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Limit {
+    /// The amount in hundredths of the currency unit.
+    pub cents: i64,
+    /// The ISO 4217 currency code.
+    pub currency: Option<String>,
+}
+
+/// Why a listing was dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    /// The price or total is below the minimum.
+    Below {
+        field: &'static str,
+        limit: Limit,
+    },
+    /// The price or total is above the maximum.
+    Above {
+        field: &'static str,
+        limit: Limit,
+    },
+    /// The price or total is bounded but unknown.
+    Unknown {
+        field: &'static str,
+    },
+    /// The price or total is in another currency than the bound.
+    Currency {
+        field: &'static str,
+        currency: String,
+    },
+    Condition(Option<Condition>),
+    MissingKeyword(String),
+    ExcludedKeyword(String),
+    MissingPattern(String),
+    ExcludedPattern(String),
+    BlockedSeller(String),
+    Format(Option<BuyingFormat>),
+    /// A rule did not match.
+    Rule {
+        rule: String,
+        outcome: Outcome,
+    },
+}
+
+/// A filter loading error.
+#[derive(Debug)]
+pub enum FilterError {
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    Toml {
+        path: PathBuf,
+        error: toml::de::Error,
+    },
+    /// A filter name was given but no configuration directory is known.
+    NoConfigDirectory,
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, error } => write!(f, "cannot read {}: {error}", path.display()),
+            Self::Toml { path, error } => write!(f, "invalid filter {}: {error}", path.display()),
+            Self::NoConfigDirectory => write!(f, "no configuration directory found"),
+        }
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+impl Filter {
+    /// Load a filter by name or path.
+    ///
+    /// A name such as `gpus` refers to `gpus.toml` in the `filters` directory
+    /// of the configuration, anything that looks like a path is read as is.
+    pub fn load(name: &str) -> Result<Self, FilterError> {
+        let path = if name.contains(std::path::MAIN_SEPARATOR) || name.ends_with(".toml") {
+            PathBuf::from(name)
+        } else {
+            filters_directory()
+                .ok_or(FilterError::NoConfigDirectory)?
+                .join(format!("{name}.toml"))
+        };
+
+        Self::read(&path)
+    }
+
+    /// Read the filter at `path`.
+    pub fn read(path: &Path) -> Result<Self, FilterError> {
+        let text = fs::read_to_string(path).map_err(|error| FilterError::Io {
+            path: path.to_owned(),
+            error,
+        })?;
+
+        toml::from_str(&text).map_err(|error| FilterError::Toml {
+            path: path.to_owned(),
+            error,
+        })
+    }
+
+    /// Whether `item` satisfies every rule.
+    pub fn matches(&self, item: &SearchItem) -> bool {
+        self.check(item).is_ok()
+    }
+
+    /// Check `item` against the rules, in the order of the fields, returning
+    /// the first one it fails.
+    pub fn check(&self, item: &SearchItem) -> Result<(), Rejection> {
+        // Check amounts
+        let price = item.price.min.as_ref();
+        check_bounds("price", price, &self.min_price, &self.max_price)?;
+        let total = item.total();
+        check_bounds("total", total.as_ref(), &self.min_total, &self.max_total)?;
+
+        // Check condition
+        if !self.conditions.is_empty()
+            && !item
+                .canonical_condition
+                .is_some_and(|condition| self.conditions.contains(&condition))
+        {
+            return Err(Rejection::Condition(item.canonical_condition));
+        }
+
+        // Check title
+        if let Some(keyword) = self
+            .keywords
+            .iter()
+            .find(|keyword| !contains_word(&item.title, keyword))
+        {
+            return Err(Rejection::MissingKeyword(keyword.clone()));
+        }
+
+        if let Some(keyword) = self
+            .exclude_keywords
+            .iter()
+            .find(|keyword| contains_word(&item.title, keyword))
+        {
+            return Err(Rejection::ExcludedKeyword(keyword.clone()));
+        }
+
+        if let Some(regex) = self
+            .title_regexes
+            .iter()
+            .find(|regex| !regex.is_match(&item.title))
+        {
+            return Err(Rejection::MissingPattern(regex.to_string()));
+        }
+
+        if let Some(regex) = self
+            .exclude_title_regexes
+            .iter()
+            .find(|regex| regex.is_match(&item.title))
+        {
+            return Err(Rejection::ExcludedPattern(regex.to_string()));
+        }
+
+        // Check seller
+        if let Some(seller) = &item.seller {
+            if self
+                .blocked_sellers
+                .iter()
+                .any(|blocked| blocked.eq_ignore_ascii_case(&seller.name))
+            {
+                return Err(Rejection::BlockedSeller(seller.name.clone()));
+            }
+        }
+
+        // Check format
+        if !self.formats.is_empty()
+            && !item
+                .buying
+                .format
+                .is_some_and(|format| self.formats.contains(&format))
+        {
+            return Err(Rejection::Format(item.buying.format));
+        }
+
+        // Check rules
+        for rule in &self.rules {
+            let outcome = rule.evaluate(item);
+
+            if !outcome.matched {
+                let rule = rule.to_string();
+                return Err(Rejection::Rule { rule, outcome });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Limit {
+    /// Whether amounts in `currency` can be compared with the limit.
+    pub(crate) fn accepts(&self, currency: &str) -> bool {
+        self.currency.as_deref().is_none_or(|own| own == currency)
+    }
+
+    /// Check that `money` is in the currency of the limit, if any.
+    fn check_currency(&self, field: &'static str, money: &Money) -> Result<(), Rejection> {
+        if self.accepts(&money.currency) {
+            Ok(())
+        } else {
+            Err(Rejection::Currency {
+                field,
+                currency: money.currency.clone(),
+            })
+        }
+    }
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:02}", self.cents / 100, self.cents % 100)?;
+
+        match &self.currency {
+            Some(currency) => write!(f, " {currency}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Limit {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid amount {text:?}, expected e.g. 500 or 12.50 EUR");
+        let captures = LIMIT_REGEX.captures(text).ok_or_else(invalid)?;
+
+        let units: i64 = captures[1].parse().map_err(|_| invalid())?;
+        let fraction = captures.get(2).map_or("", |fraction| fraction.as_str());
+        let fraction: i64 = format!("{fraction:0<2}").parse().map_err(|_| invalid())?;
+        let cents = units
+            .checked_mul(100)
+            .and_then(|cents| cents.checked_add(fraction))
+            .ok_or_else(invalid)?;
+
+        let currency = match &captures[3] {
+            "" => None,
+            marker => Some(price::currency_code(marker).ok_or_else(invalid)?.to_owned()),
+        };
+
+        Ok(Self { cents, currency })
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Below { field, limit } => write!(f, "{field} below {limit}"),
+            Self::Above { field, limit } => write!(f, "{field} above {limit}"),
+            Self::Unknown { field } => write!(f, "unknown {field}"),
+            Self::Currency { field, currency } => write!(f, "{field} in {currency}"),
+            Self::Condition(Some(condition)) => write!(f, "condition {condition} not allowed"),
+            Self::Condition(None) => write!(f, "unknown condition"),
+            Self::MissingKeyword(keyword) => write!(f, "title without {keyword:?}"),
+            Self::ExcludedKeyword(keyword) => write!(f, "title with {keyword:?}"),
+            Self::MissingPattern(regex) => write!(f, "title not matching /{regex}/"),
+            Self::ExcludedPattern(regex) => write!(f, "title matching /{regex}/"),
+            Self::BlockedSeller(seller) => write!(f, "blocked seller {seller}"),
+            Self::Format(Some(format)) => write!(f, "format {format} not allowed"),
+            Self::Format(None) => write!(f, "unknown format"),
+            Self::Rule { rule, outcome } => write!(f, "rule {rule:?} not matched: {outcome}"),
+        }
+    }
+}
+
+/// Get the directory of the named filters, `$XDG_CONFIG_HOME/ebay2atom/filters`
+/// falling back to `~/.config/ebay2atom/filters`.
+pub fn filters_directory() -> Option<PathBuf> {
+    Some(profile::config_directory()?.join("filters"))
+}
+
+fn check_bounds(
+    field: &'static str,
+    money: Option<&Money>,
+    min: &Option<Limit>,
+    max: &Option<Limit>,
+) -> Result<(), Rejection> {
+    if min.is_none() && max.is_none() {
+        return Ok(());
+    }
+
+    let money = money.ok_or(Rejection::Unknown { field })?;
+
+    if let Some(limit) = min {
+        limit.check_currency(field, money)?;
+
+        if money.cents < limit.cents {
+            let limit = limit.clone();
+            return Err(Rejection::Below { field, limit });
+        }
+    }
+
+    if let Some(limit) = max {
+        limit.check_currency(field, money)?;
+
+        if money.cents > limit.cents {
+            let limit = limit.clone();
+            return Err(Rejection::Above { field, limit });
+        }
+    }
+
+    Ok(())
+}
+
+/// Deserialize a limit written either as a number or as a string with a
+/// currency.
+fn limit<'de, D>(deserializer: D) -> Result<Option<Limit>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Amount {
+        Number(f64),
+        Text(String),
+    }
+
+    let text = match Option::<Amount>::deserialize(deserializer)? {
+        Some(Amount::Number(number)) => number.to_string(),
+        Some(Amount::Text(text)) => text,
+        None => return Ok(None),
+    };
+
+    text.parse().map(Some).map_err(de::Error::custom)
+}
+
+fn parsed_list<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|text| text.parse().map_err(de::Error::custom))
+        .collect()
+}
@@ -0,0 +1,105 @@
+//! Selector health reports, to detect when eBay changes its markup.
+
+use core::fmt;
+
+use scraper::{Html, Selector};
+
+use crate::{
+    count_item_links, parse_search_page_with,
+    profile::{compile_capturing_regex, Profile},
+    Error, Layout, DRIFT_ITEM_LINKS,
+};
+
+/// How well the selectors of a profile match a page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthReport {
+    pub layout: Layout,
+    pub search_title: bool,
+    pub results: bool,
+    /// The number of elements matching the items selector.
+    pub items: usize,
+    /// For each item field, the number of items where it was found.
+    pub fields: Vec<(&'static str, usize)>,
+    /// The number of extracted listings.
+    pub extracted: usize,
+    /// The number of skipped listings.
+    pub skipped: usize,
+    /// The number of distinct items linked from the page.
+    pub item_links: usize,
+    /// The page-level error, if any.
+    pub error: Option<Error>,
+    /// Whether the page has results but none could be extracted.
+    pub drift: bool,
+}
+
+/// Check how the selectors of `profile` match the page `html`.
+pub fn check_page(html: &str, profile: &Profile) -> HealthReport {
+    let document = Html::parse_document(html);
+    let layout = Layout::detect(&document, profile);
+    let queries = profile.queries(layout);
+    let select = |query: &str| Selector::parse(query).ok();
+    let exists = |query: &str| select(query).is_some_and(|s| document.select(&s).next().is_some());
+
+    let items: Vec<_> = select(&queries.items)
+        .map(|selector| document.select(&selector).collect())
+        .unwrap_or_default();
+
+    let fields = queries
+        .item_fields()
+        .into_iter()
+        .map(|(field, query)| {
+            let count = select(query).map_or(0, |selector| {
+                items
+                    .iter()
+                    .filter(|item| item.select(&selector).next().is_some())
+                    .count()
+            });
+
+            (field, count)
+        })
+        .collect();
+
+    let item_links = compile_capturing_regex("item_id_regex", &profile.item_id_regex)
+        .map_or(0, |id_regex| count_item_links(&document, &id_regex));
+
+    let page = parse_search_page_with(html, profile);
+    let extracted = page.as_ref().map_or(0, |page| page.items.len());
+
+    HealthReport {
+        layout,
+        search_title: exists(&profile.search_title),
+        results: exists(&queries.results),
+        items: items.len(),
+        fields,
+        extracted,
+        skipped: page.as_ref().map_or(0, |page| page.skipped.len()),
+        item_links,
+        drift: extracted == 0 && item_links >= DRIFT_ITEM_LINKS,
+        error: page.err(),
+    }
+}
+
+impl fmt::Display for HealthReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let found = |found| if found { "found" } else { "missing" };
+
+        writeln!(f, "Layout: {:?}", self.layout)?;
+        writeln!(f, "Search title: {}", found(self.search_title))?;
+        writeln!(f, "Results container: {}", found(self.results))?;
+        writeln!(f, "Items: {}", self.items)?;
+
+        for (field, count) in &self.fields {
+            writeln!(f, "  {field}: {count}/{}", self.items)?;
+        }
+
+        writeln!(f, "Extracted: {}", self.extracted)?;
+        writeln!(f, "Skipped: {}", self.skipped)?;
+        writeln!(f, "Item links: {}", self.item_links)?;
+
+        if let Some(error) = &self.error {
+            writeln!(f, "Error: {error}")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+//! Stable Atom identifiers.
+
+/// Query parameters that do not change the search results.
+const IGNORED_PARAMETERS: &[&str] = &[
+    "_from",
+    "_odkw",
+    "_osacat",
+    "_pgn",
+    "_skc",
+    "_trkparms",
+    "_trksid",
+    "amdata",
+    "campid",
+    "customid",
+    "hash",
+    "mkcid",
+    "mkevt",
+    "mkrid",
+    "rt",
+    "toolid",
+];
+
+/// Get the id of the feed for a search, see [`normalize_search_url`].
+pub fn feed_id(search_url: &str) -> String {
+    normalize_search_url(search_url)
+}
+
+/// Get the id of the entry for an item number, as a `tag:` URI (RFC 4151).
+///
+/// The id only depends on the item number, so that it survives changes in
+/// the eBay domain or in the tracking parameters of the item URL.
+pub fn entry_id(item_number: &str) -> String {
+    format!("tag:ebay.com,2024:item:{item_number}")
+}
+
+/// Normalize a search URL so that equivalent searches share the same URL.
+///
+/// The scheme is forced to `https`, the host is lowercased and stripped of
+/// its `www.` prefix, the fragment, tracking and empty parameters are removed
+/// and the remaining ones are sorted.
+pub fn normalize_search_url(url: &str) -> String {
+    let url = url.split('#').next().unwrap_or_default();
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let (location, query) = url.split_once('?').unwrap_or((url, ""));
+    let (host, path) = location.split_at(location.find('/').unwrap_or(location.len()));
+    let host = host.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+
+    let mut parameters: Vec<(&str, String)> = query
+        .split('&')
+        .filter_map(|parameter| parameter.split_once('='))
+        .filter(|(name, value)| !value.is_empty() && !IGNORED_PARAMETERS.contains(name))
+        .map(|(name, value)| (name, value.replace("%20", "+")))
+        .collect();
+
+    parameters.sort();
+
+    let query = parameters
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let path = if path.is_empty() { "/" } else { path };
+
+    if query.is_empty() {
+        format!("https://{host}{path}")
+    } else {
+        format!("https://{host}{path}?{query}")
+    }
+}
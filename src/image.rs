@@ -0,0 +1,66 @@
+//! Listing thumbnails.
+
+use scraper::ElementRef;
+
+/// Attributes holding the full image URL of lazy-loaded images, most reliable
+/// first. `src` often holds a placeholder until the image is loaded.
+const LAZY_ATTRIBUTES: &[&str] = &["data-defer-load", "data-src"];
+
+/// Image extensions and their MIME type.
+const MIME_TYPES: &[(&str, &str)] = &[
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("png", "image/png"),
+    ("webp", "image/webp"),
+    ("gif", "image/gif"),
+    ("avif", "image/avif"),
+];
+
+/// Get the image URL of an `img` element.
+///
+/// The lazy-loading attributes are tried first, then the largest candidate
+/// of `srcset` and finally `src`. Only HTTP URLs are returned, which leaves
+/// out inline placeholders.
+pub fn image_url(img: ElementRef) -> Option<String> {
+    let element = img.value();
+    let lazy = LAZY_ATTRIBUTES
+        .iter()
+        .filter_map(|attribute| element.attr(attribute));
+    let srcset = element.attr("srcset").and_then(largest_candidate);
+
+    lazy.chain(srcset)
+        .chain(element.attr("src"))
+        .map(str::trim)
+        .find(|url| url.starts_with("https://") || url.starts_with("http://"))
+        .map(str::to_owned)
+}
+
+/// Get the MIME type of an image from the extension of its URL.
+pub fn mime_type(url: &str) -> Option<&'static str> {
+    let path = url.split(['?', '#']).next()?;
+    let (_, extension) = path.rsplit_once('.')?;
+
+    MIME_TYPES
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(extension))
+        .map(|&(_, mime_type)| mime_type)
+}
+
+/// Get the URL of the largest candidate of a `srcset`, such as `a.jpg 1x,
+/// b.jpg 2x` or `a.jpg 140w, b.jpg 500w`.
+fn largest_candidate(srcset: &str) -> Option<&str> {
+    srcset
+        .split(',')
+        .filter_map(|candidate| {
+            let mut parts = candidate.split_whitespace();
+            let url = parts.next()?;
+            let size = parts
+                .next()
+                .and_then(|descriptor| descriptor.strip_suffix(['w', 'x'])?.parse::<f64>().ok())
+                .unwrap_or(1.0);
+
+            Some((url, size))
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(url, _)| url)
+}
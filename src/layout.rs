@@ -0,0 +1,41 @@
+//! The supported markups of search results pages.
+
+use core::cmp::Ordering;
+
+use scraper::{Html, Selector};
+
+use crate::profile::Profile;
+
+/// The markup of a search results page, each one has its own selectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Layout {
+    /// Listings in `s-item` elements.
+    Classic,
+    /// Listings in `s-card` elements, rolled out in many regions.
+    Card,
+}
+
+impl Layout {
+    /// Detect the layout of `document`.
+    ///
+    /// The layout with the most listings wins, with no listings at all the
+    /// card layout is only chosen when its results container is the only
+    /// one on the page.
+    pub fn detect(document: &Html, profile: &Profile) -> Self {
+        let count = |query: &str| {
+            Selector::parse(query).map_or(0, |selector| document.select(&selector).count())
+        };
+
+        match count(&profile.card.items).cmp(&count(&profile.classic.items)) {
+            Ordering::Greater => Self::Card,
+            Ordering::Less => Self::Classic,
+            Ordering::Equal => {
+                if count(&profile.classic.results) == 0 && count(&profile.card.results) > 0 {
+                    Self::Card
+                } else {
+                    Self::Classic
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,467 @@
+//! Extraction of listings from eBay search result pages.
+//!
+//! The entry point is [`parse_search_page`], which turns the HTML of a search
+//! page into a [`SearchPage`] holding one [`SearchItem`] per listing.
+
+pub mod buying;
+pub mod condition;
+mod error;
+pub mod fetch;
+pub mod filter;
+pub mod health;
+pub mod id;
+pub mod image;
+pub mod layout;
+pub mod price;
+pub mod profile;
+pub mod render;
+pub mod rule;
+pub mod section;
+pub mod seller;
+pub mod shipping;
+pub mod site;
+pub mod state;
+pub mod time_left;
+pub mod title;
+pub mod vocabulary;
+pub mod xml;
+
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::Duration;
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::{profile::Queries, vocabulary::Vocabulary};
+
+pub use buying::{Buying, BuyingFormat};
+pub use condition::Condition;
+pub use error::{Error, ItemError, SkippedItem};
+pub use filter::Filter;
+pub use layout::Layout;
+pub use price::{Money, Price};
+pub use profile::Profile;
+pub use rule::Rule;
+pub use section::Section;
+pub use seller::Seller;
+pub use shipping::{Shipping, ShippingCost};
+pub use site::Site;
+pub use title::Title;
+
+// eBay-specific constants
+pub const EBAY_SEARCH_RESULTS: usize = 71;
+pub const DRIFT_ITEM_LINKS: usize = 3;
+pub const FEED_TITLE_QUERY: &str = r#"input[name="_nkw"]"#;
+pub const RESULTS_QUERY: &str = ".srp-river .srp-river-results";
+pub const ITEMS_QUERY: &str = ".srp-river .srp-river-results .s-item__wrapper";
+pub const SECTIONS_QUERY: &str = ".srp-river .srp-river-results .srp-river-answer";
+pub const TITLE_QUERY: &str = ".s-item__title span[role=heading]";
+pub const LINK_QUERY: &str = ".s-item__link";
+pub const PRICE_QUERY: &str = ".s-item__price";
+pub const CONDITION_QUERY: &str = ".SECONDARY_INFO";
+pub const TIME_LEFT_QUERY: &str = ".s-item__time-left";
+pub const PURCHASE_OPTIONS_QUERY: &str = ".s-item__purchase-options";
+pub const AD_QUERY: &str = ".lvformat";
+pub const BIDS_QUERY: &str = ".s-item__bids";
+pub const SHIPPING_QUERY: &str = ".s-item__shipping, .s-item__freeXDays";
+pub const SELLER_QUERY: &str = ".s-item__seller-info-text";
+pub const IMAGE_QUERY: &str = ".s-item__image-wrapper img, .s-item__image img";
+pub const SPONSORED_QUERY: &str = ".s-item__sep, .s-item__sponsored";
+pub const CARD_RESULTS_QUERY: &str = ".srp-results";
+pub const CARD_ITEMS_QUERY: &str = ".srp-results .s-card";
+pub const CARD_SECTIONS_QUERY: &str = ".srp-results .srp-river-answer";
+pub const CARD_TITLE_QUERY: &str = ".s-card__title";
+pub const CARD_LINK_QUERY: &str = "a.su-link";
+pub const CARD_PRICE_QUERY: &str = ".s-card__price";
+pub const CARD_CONDITION_QUERY: &str = ".s-card__subtitle";
+pub const CARD_TIME_LEFT_QUERY: &str = ".s-card__time-left";
+pub const CARD_PURCHASE_OPTIONS_QUERY: &str = ".s-card__purchase-options";
+pub const CARD_AD_QUERY: &str = ".s-card__format";
+pub const CARD_BIDS_QUERY: &str = ".s-card__bids";
+pub const CARD_SHIPPING_QUERY: &str = ".s-card__shipping";
+pub const CARD_SELLER_QUERY: &str = ".s-card__seller-info";
+pub const CARD_IMAGE_QUERY: &str = "img.s-card__image";
+pub const CARD_SPONSORED_QUERY: &str = ".s-card__footer, .s-card__sponsored";
+pub const PAGINATION_QUERY: &str = r#"a[href*="_pgn="]"#;
+pub const BASE_URL_REGEX: &str = r#""baseUrl":"(https://[^"]+)""#;
+pub const ITEM_URL_REGEX: &str = r"https?://[^/?#]+/itm/(?:[^/?#]*/)?\d+";
+pub const ITEM_ID_REGEX: &str = r"/itm/(?:[^/?#]*/)?(\d+)";
+pub const PAGE_NUMBER_REGEX: &str = r"[?&]_pgn=(\d+)";
+/// The dummy item number of the "Shop on eBay" placeholder listing.
+pub const PLACEHOLDER_ITEM_ID: &str = "123456";
+pub const PLACEHOLDER_TITLE: &str = "Shop on eBay";
+
+/// A parsed search results page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchPage {
+    /// The search keywords, as shown in the search box.
+    pub title: String,
+    /// The URL of the search.
+    pub url: String,
+    /// The eBay site of the search, from the domain of its URL.
+    pub site: Site,
+    /// The markup the listings were extracted from.
+    pub layout: Layout,
+    /// The listings, in page order.
+    pub items: Vec<SearchItem>,
+    /// The listings that could not be extracted.
+    pub skipped: Vec<SkippedItem>,
+    /// The links to other result pages, by page number.
+    pub pages: BTreeMap<u32, String>,
+    /// The number of distinct items linked from the page, listings or not.
+    pub item_links: usize,
+    /// The number of listings left out by [`SearchPage::retain`].
+    pub excluded: usize,
+}
+
+impl SearchPage {
+    /// Append the listings of a following page, skipping the ones that are
+    /// already present.
+    pub fn merge(&mut self, page: SearchPage) {
+        let mut ids: HashSet<_> = self.items.iter().map(|item| item.id.clone()).collect();
+        let items = page
+            .items
+            .into_iter()
+            .filter(|item| ids.insert(item.id.clone()));
+
+        self.items.extend(items);
+        self.skipped.extend(page.skipped);
+        self.pages.extend(page.pages);
+        self.item_links += page.item_links;
+        self.excluded += page.excluded;
+    }
+
+    /// Only keep the listings for which `keep` returns `true`.
+    pub fn retain(&mut self, keep: impl FnMut(&SearchItem) -> bool) {
+        let count = self.items.len();
+        self.items.retain(keep);
+        self.excluded += count - self.items.len();
+    }
+
+    /// Whether the page links to items but none could be extracted, which
+    /// means the selectors no longer match the eBay markup.
+    pub fn is_drifted(&self) -> bool {
+        self.items.is_empty() && self.excluded == 0 && self.item_links >= DRIFT_ITEM_LINKS
+    }
+}
+
+/// A single listing on a search results page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchItem {
+    pub title: String,
+    /// Whether the listing carries a "New Listing" badge.
+    pub new_listing: bool,
+    /// The eBay item number.
+    pub id: String,
+    pub url: String,
+    /// The section of the results the listing was found in.
+    pub section: Section,
+    pub price: Price,
+    pub shipping: Option<Shipping>,
+    /// The condition as shown on the page.
+    pub condition: Option<String>,
+    /// The condition mapped to its canonical value, if recognized.
+    pub canonical_condition: Option<Condition>,
+    pub time_left: Option<String>,
+    /// The time left, parsed relative to when the page was downloaded.
+    pub ends_in: Option<Duration>,
+    pub purchase_options: Option<String>,
+    pub ad: Option<String>,
+    pub seller: Option<Seller>,
+    /// The thumbnail URL.
+    pub image: Option<String>,
+    /// The buying format, parsed from the purchase options, ad label and bid
+    /// count.
+    pub buying: Buying,
+    /// Whether the listing is marked as sponsored.
+    pub sponsored: bool,
+}
+
+impl SearchItem {
+    /// Get the total price with shipping, `None` when the shipping cost is
+    /// unknown. For multi-variation listings this is the lowest total.
+    pub fn total(&self) -> Option<Money> {
+        self.shipping.as_ref()?.total(self.price.min.as_ref()?)
+    }
+}
+
+/// Compiled selectors and regexes for the item fields.
+struct ItemSelectors {
+    title: Selector,
+    link: Selector,
+    price: Selector,
+    shipping: Selector,
+    condition: Selector,
+    time_left: Selector,
+    purchase_options: Selector,
+    ad: Selector,
+    bids: Selector,
+    seller: Selector,
+    image: Selector,
+    sponsored: Selector,
+    url: Regex,
+    id: Regex,
+}
+
+impl ItemSelectors {
+    fn new(queries: &Queries, profile: &Profile) -> Result<Self, Error> {
+        Ok(Self {
+            title: selector("title", &queries.title)?,
+            link: selector("link", &queries.link)?,
+            price: selector("price", &queries.price)?,
+            shipping: selector("shipping", &queries.shipping)?,
+            condition: selector("condition", &queries.condition)?,
+            time_left: selector("time_left", &queries.time_left)?,
+            purchase_options: selector("purchase_options", &queries.purchase_options)?,
+            ad: selector("ad", &queries.ad)?,
+            bids: selector("bids", &queries.bids)?,
+            seller: selector("seller", &queries.seller)?,
+            image: selector("image", &queries.image)?,
+            sponsored: selector("sponsored", &queries.sponsored)?,
+            url: regex("item_url_regex", &profile.item_url_regex)?,
+            id: capturing_regex("item_id_regex", &profile.item_id_regex)?,
+        })
+    }
+}
+
+fn selector(field: &str, query: &str) -> Result<Selector, Error> {
+    profile::compile_selector(field, query)
+        .map_err(|error| Error::InvalidProfile(error.to_string()))
+}
+
+fn regex(field: &str, regex: &str) -> Result<Regex, Error> {
+    profile::compile_regex(field, regex).map_err(|error| Error::InvalidProfile(error.to_string()))
+}
+
+fn capturing_regex(field: &str, regex: &str) -> Result<Regex, Error> {
+    profile::compile_capturing_regex(field, regex)
+        .map_err(|error| Error::InvalidProfile(error.to_string()))
+}
+
+/// Parse the HTML of an eBay search results page with the built-in profile.
+///
+/// Items that cannot be extracted are reported in [`SearchPage::skipped`]
+/// instead of failing the whole page.
+pub fn parse_search_page(html: &str) -> Result<SearchPage, Error> {
+    parse_search_page_with(html, &Profile::default())
+}
+
+/// Parse the HTML of an eBay search results page with a custom profile.
+pub fn parse_search_page_with(html: &str, profile: &Profile) -> Result<SearchPage, Error> {
+    let document = Html::parse_document(html);
+
+    // Get feed data
+    let title = document
+        .select(&selector("search_title", &profile.search_title)?)
+        .next()
+        .and_then(|input| input.value().attr("value"))
+        .map(xml::sanitize)
+        .ok_or(Error::MissingSearchTitle)?;
+
+    // Get feed link
+    let link_regex = capturing_regex("base_url_regex", &profile.base_url_regex)?;
+    let url = &link_regex.captures(html).ok_or(Error::MissingSearchUrl)?[1];
+    let url = xml::sanitize(&unescape_url(url));
+
+    // Get site
+    let site = Site::from_url(&url).unwrap_or_default();
+    let vocabulary = site.vocabulary();
+
+    // Get layout
+    let layout = Layout::detect(&document, profile);
+    let queries = profile.queries(layout);
+
+    // Check results container
+    if document
+        .select(&selector("results", &queries.results)?)
+        .next()
+        .is_none()
+    {
+        return Err(Error::MissingResults);
+    }
+
+    // Parse items
+    let selectors = ItemSelectors::new(queries, profile)?;
+    let mut items = Vec::with_capacity(EBAY_SEARCH_RESULTS);
+    let mut skipped = Vec::new();
+    let mut section = Section::Exact;
+    let mut position = 0;
+
+    // Walk items and section headings in page order
+    let sections = selector("sections", &queries.sections)?;
+    let elements = selector("items", &format!("{}, {}", queries.items, queries.sections))?;
+
+    for element in document.select(&elements) {
+        if sections.matches(&element) {
+            let heading: String = element.text().collect();
+            section = Section::from_heading(&heading, vocabulary).unwrap_or(section);
+            continue;
+        }
+
+        position += 1;
+
+        if is_placeholder(element, &selectors) {
+            continue;
+        }
+
+        match parse_item(element, &selectors, site) {
+            Ok(item) => items.push(SearchItem { section, ..item }),
+            Err(error) => skipped.push(SkippedItem { position, error }),
+        }
+    }
+
+    // Count item links
+    let item_links = count_item_links(&document, &selectors.id);
+
+    // Get pagination
+    let page_number_regex = capturing_regex("page_number_regex", &profile.page_number_regex)?;
+    let pages = document
+        .select(&selector("pagination", &profile.pagination)?)
+        .filter_map(|link| link.value().attr("href"))
+        .filter(|href| href.starts_with("http"))
+        .filter_map(|href| {
+            let number = page_number_regex.captures(href)?[1].parse().ok()?;
+            Some((number, xml::sanitize(href)))
+        })
+        .collect();
+
+    Ok(SearchPage {
+        title,
+        url,
+        site,
+        layout,
+        items,
+        skipped,
+        pages,
+        item_links,
+        excluded: 0,
+    })
+}
+
+/// Count the distinct item numbers linked from `document`.
+pub(crate) fn count_item_links(document: &Html, id_regex: &Regex) -> usize {
+    let links = Selector::parse("a[href]").unwrap();
+
+    document
+        .select(&links)
+        .filter_map(|link| id_regex.captures(link.value().attr("href")?))
+        .map(|captures| captures[1].to_owned())
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// Unescape the characters a URL may have escaped in the page JSON data.
+fn unescape_url(url: &str) -> String {
+    url.replace("\\u0026", "&").replace("\\/", "/")
+}
+
+fn parse_item(
+    item: ElementRef,
+    selectors: &ItemSelectors,
+    site: Site,
+) -> Result<SearchItem, ItemError> {
+    let vocabulary = site.vocabulary();
+
+    // Get title
+    let title = item
+        .select(&selectors.title)
+        .next()
+        .map(|title| Title::parse(title.text(), vocabulary))
+        .filter(|title| !title.text.is_empty())
+        .ok_or(ItemError::MissingTitle)?;
+
+    // Get item link/id
+    let item_url = item
+        .select(&selectors.link)
+        .next()
+        .and_then(|link| link.value().attr("href"))
+        .ok_or(ItemError::MissingLink)?;
+
+    let invalid_link = || ItemError::InvalidLink(item_url.to_owned());
+    let url = selectors
+        .url
+        .find(item_url)
+        .ok_or_else(invalid_link)?
+        .as_str();
+    let id = selectors.id.captures(url).ok_or_else(invalid_link)?[1].to_owned();
+
+    // Get price
+    let price = first_text(item, &selectors.price).ok_or(ItemError::MissingPrice)?;
+    let price = Price::parse_on(&price, site);
+
+    let condition = first_text(item, &selectors.condition);
+    let time_left = first_text(item, &selectors.time_left);
+    let purchase_options = first_text(item, &selectors.purchase_options);
+    let ad = first_text(item, &selectors.ad);
+    let bids = first_text(item, &selectors.bids);
+    let buying = Buying::parse(
+        purchase_options.as_deref(),
+        ad.as_deref(),
+        bids.as_deref(),
+        vocabulary,
+    );
+
+    Ok(SearchItem {
+        title: xml::sanitize(&title.text),
+        new_listing: title.new_listing,
+        id,
+        url: url.to_owned(),
+        section: Section::Exact,
+        price,
+        shipping: first_text(item, &selectors.shipping)
+            .map(|shipping| Shipping::parse(&shipping, site)),
+        canonical_condition: condition
+            .as_deref()
+            .and_then(|condition| Condition::parse(condition, vocabulary)),
+        condition,
+        ends_in: time_left
+            .as_deref()
+            .and_then(|time_left| time_left::parse(time_left, vocabulary)),
+        time_left,
+        purchase_options,
+        ad,
+        seller: first_text(item, &selectors.seller).and_then(|seller| Seller::parse(&seller)),
+        image: item
+            .select(&selectors.image)
+            .find_map(image::image_url)
+            .map(|url| xml::sanitize(&url)),
+        buying,
+        sponsored: is_sponsored(item, &selectors.sponsored, vocabulary),
+    })
+}
+
+/// Whether `item` is the "Shop on eBay" placeholder, which is not a listing.
+fn is_placeholder(item: ElementRef, selectors: &ItemSelectors) -> bool {
+    let placeholder_id = item
+        .select(&selectors.link)
+        .next()
+        .and_then(|link| selectors.id.captures(link.value().attr("href")?))
+        .is_some_and(|captures| &captures[1] == PLACEHOLDER_ITEM_ID);
+
+    let placeholder_title = item
+        .select(&selectors.title)
+        .next()
+        .is_some_and(|title| title.text().collect::<String>().trim() == PLACEHOLDER_TITLE);
+
+    placeholder_id || placeholder_title
+}
+
+/// Whether a sponsored label is found in `item`, compared with whitespace
+/// removed as eBay spaces out its letters.
+fn is_sponsored(item: ElementRef, selector: &Selector, vocabulary: &Vocabulary) -> bool {
+    item.select(selector).any(|element| {
+        let text: String = element
+            .text()
+            .flat_map(str::chars)
+            .filter(|c| !c.is_whitespace())
+            .collect();
+
+        vocabulary::contains(&text, vocabulary.sponsored)
+    })
+}
+
+/// Get the first text node of the first element matching `selector`.
+fn first_text(item: ElementRef, selector: &Selector) -> Option<String> {
+    let element = item.select(selector).next()?;
+    element.text().next().map(xml::sanitize)
+}
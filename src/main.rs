@@ -1,182 +1,598 @@
-use core::fmt::Write;
+use core::fmt;
 use std::{
     io::{self, Read},
-    time::SystemTime,
+    path::PathBuf,
+    process::ExitCode,
+    time::Duration,
 };
 
-use atom_syndication::{
-    Content, Entry, FeedBuilder, GeneratorBuilder, LinkBuilder, TextBuilder, TextType, WriteConfig,
+use chrono::Utc;
+use clap::{Parser, Subcommand, ValueEnum};
+use ebay2atom::{
+    fetch::{self, FetchConfig, FetchError, Fetcher},
+    filter::{Filter, FilterError, Limit},
+    health, id, parse_search_page_with,
+    profile::{Profile, ProfileError},
+    render::{self, atom, csv, json_feed, ndjson, rss, Feed},
+    state,
+    state::StateStore,
+    BuyingFormat, Condition, Rule, SearchItem, SearchPage, Section,
 };
-use chrono::{DateTime, Local};
 use regex::Regex;
-use scraper::{Html, Selector};
 
 // Manifest environment variables
-const VERSION: &str = env!("CARGO_PKG_VERSION");
-const REPOSITORY: &str = env!("CARGO_PKG_REPOSITORY");
 const NAME: &str = env!("CARGO_PKG_NAME");
 
-// eBay-specific constants
-const EBAY_SEARCH_RESULTS: usize = 71;
-const FEED_TITLE_QUERY: &str = r#"input[name="_nkw"]"#;
-const ITEMS_QUERY: &str = ".srp-river .srp-river-results .s-item__wrapper";
-const TITLE_QUERY: &str = ".s-item__title span[role=heading]";
-const LINK_QUERY: &str = ".s-item__link";
-const PRICE_QUERY: &str = ".s-item__price";
-const CONDITION_QUERY: &str = ".SECONDARY_INFO";
-const TIME_LEFT_QUERY: &str = ".s-item__time-left";
-const PURCHASE_OPTIONS_QUERY: &str = ".s-item__purchase-options";
-const AD_QUERY: &str = ".lvformat";
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Get document
-    let mut html = String::new();
-    io::stdin().read_to_string(&mut html)?;
-    let document = Html::parse_document(&html);
-
-    // Get feed data
-    let feed_title_selector = Selector::parse(FEED_TITLE_QUERY)?;
-    let feed_title_input = document.select(&feed_title_selector).next().unwrap();
-    let feed_title = feed_title_input.value().attr("value").unwrap();
-
-    // Get feed link
-    let link_regex = Regex::new(r#"baseUrl":"(https://[^&]+).*?""#)?;
-    let feed_link = link_regex.captures(&html).unwrap().get(1).unwrap().as_str();
-
-    // Get generator
-    let generator = GeneratorBuilder::default()
-        .uri(Some(REPOSITORY.to_owned()))
-        .version(Some(VERSION.to_owned()))
-        .value(NAME.to_owned())
-        .build();
-
-    // Get links
-    let feed_link = LinkBuilder::default()
-        .rel("alternate".to_owned())
-        .mime_type(Some("text/html".to_owned()))
-        .href(feed_link.to_owned())
-        .build();
-
-    // Get title
-    let feed_title = TextBuilder::default()
-        .r#type(TextType::Text)
-        .value(feed_title.to_owned())
-        .build();
-
-    // Get local DateTime
-    let update_time: DateTime<Local> = SystemTime::now().into();
-
-    // Build feed (except entries)
-    let mut feed = FeedBuilder::default()
-        .generator(Some(generator))
-        .links(vec![feed_link])
-        .title(feed_title)
-        .updated(update_time)
-        .build();
-
-    // Get item selectors and regexes
-    let title_selector = Selector::parse(TITLE_QUERY)?;
-    let link_selector = Selector::parse(LINK_QUERY)?;
-    let price_selector = Selector::parse(PRICE_QUERY)?;
-    let condition_selector = Selector::parse(CONDITION_QUERY)?;
-    let time_left_selector = Selector::parse(TIME_LEFT_QUERY)?;
-    let purchase_options_selector = Selector::parse(PURCHASE_OPTIONS_QUERY)?;
-    let ad_selector = Selector::parse(AD_QUERY)?;
-    let items_selector = Selector::parse(ITEMS_QUERY)?;
-    let url_regex = Regex::new(r"https.+(\d{10})")?;
-
-    // Store the entries array
-    let mut entries: Vec<Entry> = Vec::with_capacity(EBAY_SEARCH_RESULTS);
-
-    // Parse feed items
-    for item in document.select(&items_selector) {
-        let mut entry = Entry::default();
-        let mut content = Content::default();
-        content.set_content_type(Some("xhtml".to_owned()));
-        let mut description = r#"<div xmlns="http://www.w3.org/1999/xhtml">"#.to_owned();
-
-        // Get title
-        let title = item
-            .select(&title_selector)
-            .next()
-            .unwrap()
-            .text()
-            .last()
-            .unwrap();
-
-        entry.set_title(title);
-
-        // Get item link/id
-        let item_url = item
-            .select(&link_selector)
-            .next()
-            .unwrap()
-            .value()
-            .attr("href")
-            .unwrap();
-
-        let url_captures = url_regex.captures(item_url).unwrap();
-        let item_url = url_captures.get(0).unwrap().as_str();
-
-        let link = LinkBuilder::default()
-            .rel("alternate".to_owned())
-            .mime_type(Some("text/html".to_owned()))
-            .href(item_url.to_owned())
-            .build();
-
-        entry.set_links([link]);
-        entry.set_id(item_url);
-
-        // Get price
-        let price = item
-            .select(&price_selector)
-            .next()
-            .unwrap()
-            .text()
-            .next()
-            .unwrap();
-
-        write!(description, "<p>Price: {price}</p>")?;
-
-        // Get condition
-        if let Some(condition) = item.select(&condition_selector).next() {
-            let condition = condition.text().next().unwrap();
-            write!(description, "<p>Condition: {condition}</p>")?;
-        }
-
-        // Get time left
-        if let Some(time_left) = item.select(&time_left_selector).next() {
-            let time_left = time_left.text().next().unwrap();
-            write!(description, "<p>Time left: {time_left}</p>")?;
-        }
-
-        // Get purchase options
-        if let Some(purchase_options) = item.select(&purchase_options_selector).next() {
-            let purchase_options = purchase_options.text().next().unwrap();
-            write!(description, "<p>Purchase options: {purchase_options}</p>")?;
-        }
-
-        // Get ad
-        if let Some(ad) = item.select(&ad_selector).next() {
-            let ad = ad.text().next().unwrap();
-            write!(description, "<p>Ad: {ad}</p>")?;
-        }
-
-        // Finish and append entry
-        description.push_str("</div>");
-        content.set_value(description);
-        entry.set_content(content);
-        entry.set_updated(update_time);
-        entries.push(entry);
-    }
-
-    feed.set_entries(entries);
-
-    let write_config = WriteConfig {
-        write_document_declaration: true,
-        indent_size: Some(2),
+const DRIFT_WARNING: &str = "the page has results but no listing matched the selectors, \
+    the eBay markup probably changed (see --check and --profile)";
+
+/// Generate a feed from an eBay search page read from stdin.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// The output format
+    #[arg(long, value_enum, default_value_t = Format::Atom, global = true)]
+    format: Format,
+
+    /// File recording when items were first seen and last changed [default:
+    /// one file per feed in $XDG_STATE_HOME/ebay2atom]
+    #[arg(long, value_name = "PATH", global = true)]
+    state: Option<PathBuf>,
+
+    /// Do not record items, every entry is then updated on each run
+    #[arg(long, conflicts_with = "state", global = true)]
+    no_state: bool,
+
+    /// Selector profile, either a name in $XDG_CONFIG_HOME/ebay2atom/profiles
+    /// or the path of a TOML file [default: built-in]
+    #[arg(long, value_name = "NAME|PATH", global = true)]
+    profile: Option<String>,
+
+    /// Report how the selectors match the page instead of writing the feed
+    #[arg(long, global = true)]
+    check: bool,
+
+    /// Sections of the results to include: exact, fewer-words (results
+    /// matching fewer words) or international (results from international
+    /// sellers)
+    #[arg(
+        long,
+        value_name = "SECTION,...",
+        value_delimiter = ',',
+        default_value = "exact",
+        global = true
+    )]
+    sections: Vec<Section>,
+
+    /// Keep the sponsored listings, labeled as such in their content
+    #[arg(long, global = true)]
+    sponsored: bool,
+
+    /// The order of the entries
+    #[arg(long, value_enum, default_value_t = Sort::Page, global = true)]
+    sort: Sort,
+
+    /// Print why each listing is kept or dropped on the standard error
+    #[arg(long, global = true)]
+    explain: bool,
+
+    #[command(flatten)]
+    filter: FilterArgs,
+}
+
+impl Args {
+    /// Check whether `item` belongs in the feed, returning why not.
+    fn check(&self, item: &SearchItem, filter: &Filter) -> Result<(), String> {
+        if !self.sections.contains(&item.section) {
+            return Err(format!("section {} not included", item.section));
+        }
+
+        if item.sponsored && !self.sponsored {
+            return Err("sponsored".to_owned());
+        }
+
+        filter
+            .check(item)
+            .map_err(|rejection| rejection.to_string())
+    }
+
+    /// Whether `item` belongs in the feed, printing why with `--explain`.
+    fn keep(&self, item: &SearchItem, filter: &Filter) -> bool {
+        let verdict = self.check(item, filter);
+
+        if self.explain {
+            let (action, reason) = match &verdict {
+                Ok(()) => {
+                    let outcomes: Vec<_> = filter
+                        .rules
+                        .iter()
+                        .map(|rule| rule.evaluate(item).to_string())
+                        .collect();
+                    let reason = if outcomes.is_empty() {
+                        "no rule to match".to_owned()
+                    } else {
+                        outcomes.join(", ")
+                    };
+                    ("kept", reason)
+                }
+                Err(reason) => ("dropped", reason.clone()),
+            };
+
+            eprintln!("{NAME}: {action} {} {:?}: {reason}", item.id, item.title);
+        }
+
+        verdict.is_ok()
+    }
+}
+
+/// Rules the listings must satisfy, added to the ones of the `--filter` file.
+#[derive(Debug, clap::Args)]
+#[command(next_help_heading = "Filters")]
+struct FilterArgs {
+    /// Filter file, either a name in $XDG_CONFIG_HOME/ebay2atom/filters or the
+    /// path of a TOML file
+    #[arg(long, value_name = "NAME|PATH", global = true)]
+    filter: Option<String>,
+
+    /// Drop the listings cheaper than this, e.g. 20 or 20.00 EUR
+    #[arg(long, value_name = "AMOUNT", global = true)]
+    min_price: Option<Limit>,
+
+    /// Drop the listings more expensive than this
+    #[arg(long, value_name = "AMOUNT", global = true)]
+    max_price: Option<Limit>,
+
+    /// Drop the listings cheaper than this with shipping
+    #[arg(long, value_name = "AMOUNT", global = true)]
+    min_total: Option<Limit>,
+
+    /// Drop the listings more expensive than this with shipping, or whose
+    /// shipping is unknown
+    #[arg(long, value_name = "AMOUNT", global = true)]
+    max_total: Option<Limit>,
+
+    /// Only keep these conditions: new, open-box, refurbished, used or
+    /// for-parts
+    #[arg(
+        long = "condition",
+        value_name = "CONDITION,...",
+        value_delimiter = ',',
+        global = true
+    )]
+    conditions: Vec<Condition>,
+
+    /// Only keep the titles containing this word
+    #[arg(long = "keyword", value_name = "WORD", global = true)]
+    keywords: Vec<String>,
+
+    /// Drop the titles containing this word
+    #[arg(long = "exclude-keyword", value_name = "WORD", global = true)]
+    exclude_keywords: Vec<String>,
+
+    /// Only keep the titles matching this regex
+    #[arg(long = "title-regex", value_name = "REGEX", global = true)]
+    title_regexes: Vec<Regex>,
+
+    /// Drop the titles matching this regex
+    #[arg(long = "exclude-title-regex", value_name = "REGEX", global = true)]
+    exclude_title_regexes: Vec<Regex>,
+
+    /// Drop the listings of this seller
+    #[arg(long = "block-seller", value_name = "SELLER", global = true)]
+    blocked_sellers: Vec<String>,
+
+    /// Only keep these buying formats: auction, buy-it-now, best-offer or
+    /// classified
+    #[arg(
+        long = "buying-format",
+        value_name = "FORMAT,...",
+        value_delimiter = ',',
+        global = true
+    )]
+    formats: Vec<BuyingFormat>,
+
+    /// Only keep the listings matching this rule, e.g. 'total < 500 EUR and
+    /// not condition == "for parts"'
+    #[arg(long = "rule", value_name = "EXPR", global = true)]
+    rules: Vec<Rule>,
+}
+
+impl FilterArgs {
+    /// Load the filter file, if any, and add the rules given on the command
+    /// line. Bounds and allowed values replace the ones of the file.
+    fn filter(&self) -> Result<Filter, FilterError> {
+        let mut filter = match &self.filter {
+            Some(name) => Filter::load(name)?,
+            None => Filter::default(),
+        };
+
+        let bounds = [
+            (&mut filter.min_price, &self.min_price),
+            (&mut filter.max_price, &self.max_price),
+            (&mut filter.min_total, &self.min_total),
+            (&mut filter.max_total, &self.max_total),
+        ];
+
+        for (bound, arg) in bounds {
+            if arg.is_some() {
+                bound.clone_from(arg);
+            }
+        }
+
+        if !self.conditions.is_empty() {
+            filter.conditions.clone_from(&self.conditions);
+        }
+
+        if !self.formats.is_empty() {
+            filter.formats.clone_from(&self.formats);
+        }
+
+        filter.keywords.extend(self.keywords.iter().cloned());
+        filter
+            .exclude_keywords
+            .extend(self.exclude_keywords.iter().cloned());
+        filter
+            .title_regexes
+            .extend(self.title_regexes.iter().cloned());
+        filter
+            .exclude_title_regexes
+            .extend(self.exclude_title_regexes.iter().cloned());
+        filter
+            .blocked_sellers
+            .extend(self.blocked_sellers.iter().cloned());
+        filter.rules.extend(self.rules.iter().cloned());
+
+        Ok(filter)
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    /// Atom 1.0
+    Atom,
+    /// RSS 2.0
+    Rss,
+    /// JSON Feed 1.1
+    #[value(name = "jsonfeed")]
+    JsonFeed,
+    /// One JSON object per listing and line
+    Ndjson,
+    /// One CSV row per listing
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Sort {
+    /// As listed on the search page
+    Page,
+    /// The auctions ending soonest first
+    Ending,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Download the search page instead of reading it from stdin
+    Fetch(FetchArgs),
+    /// Print the selector profile as TOML, as a starting point for a custom one
+    Profile,
+}
+
+#[derive(Debug, clap::Args)]
+struct FetchArgs {
+    /// The eBay search URL
+    url: String,
+
+    /// The User-Agent header sent to eBay
+    #[arg(long, value_name = "AGENT", default_value = fetch::USER_AGENT)]
+    user_agent: String,
+
+    /// Timeout of each attempt
+    #[arg(long, value_name = "SECONDS", default_value = "30", value_parser = seconds)]
+    timeout: Duration,
+
+    /// How many times a failed download is retried
+    #[arg(long, value_name = "COUNT", default_value_t = 3)]
+    retries: u32,
+
+    /// Delay before the first retry, doubled on each further retry
+    #[arg(long, value_name = "SECONDS", default_value = "1", value_parser = seconds)]
+    backoff: Duration,
+
+    /// JSON file to load cookies from and save them to
+    #[arg(long, value_name = "PATH")]
+    cookie_jar: Option<PathBuf>,
+
+    /// Follow the pagination up to this many result pages
+    #[arg(long, value_name = "COUNT", default_value_t = 1)]
+    pages: u32,
+
+    /// Stop once this many listings are collected
+    #[arg(long, value_name = "COUNT")]
+    max_items: Option<usize>,
+}
+
+impl FetchArgs {
+    fn config(&self) -> FetchConfig {
+        FetchConfig {
+            user_agent: self.user_agent.clone(),
+            timeout: self.timeout,
+            retries: self.retries,
+            backoff: self.backoff,
+            cookie_jar: self.cookie_jar.clone(),
+        }
+    }
+}
+
+/// Parse a number of seconds such as `30` or `0.5`.
+fn seconds(text: &str) -> Result<Duration, String> {
+    let seconds: f64 = text
+        .parse()
+        .map_err(|_| "expected a number of seconds".to_owned())?;
+    Duration::try_from_secs_f64(seconds).map_err(|error| error.to_string())
+}
+
+/// A fatal error, each variant maps to its own exit code.
+#[derive(Debug)]
+enum AppError {
+    Io(io::Error),
+    Fetch(FetchError),
+    Profile(ProfileError),
+    Filter(FilterError),
+    Page(ebay2atom::Error),
+    Feed(render::Error),
+    /// The page has results but none could be extracted.
+    Drift,
+}
+
+impl AppError {
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            Self::Io(_) => ExitCode::from(1),
+            Self::Page(ebay2atom::Error::MissingSearchTitle) => ExitCode::from(2),
+            Self::Page(ebay2atom::Error::MissingSearchUrl) => ExitCode::from(3),
+            Self::Page(ebay2atom::Error::MissingResults) => ExitCode::from(4),
+            Self::Profile(_) | Self::Page(ebay2atom::Error::InvalidProfile(_)) => ExitCode::from(7),
+            Self::Feed(_) => ExitCode::from(5),
+            Self::Fetch(_) => ExitCode::from(6),
+            Self::Drift => ExitCode::from(8),
+            Self::Filter(_) => ExitCode::from(9),
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "I/O error: {error}"),
+            Self::Fetch(error) => write!(f, "download failed: {error}"),
+            Self::Profile(error) => write!(f, "cannot load profile: {error}"),
+            Self::Filter(error) => write!(f, "cannot load filter: {error}"),
+            Self::Page(error) => write!(f, "invalid search page: {error}"),
+            Self::Feed(error) => write!(f, "cannot write feed: {error}"),
+            Self::Drift => write!(f, "{DRIFT_WARNING}"),
+        }
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<FetchError> for AppError {
+    fn from(error: FetchError) -> Self {
+        Self::Fetch(error)
+    }
+}
+
+impl From<ebay2atom::Error> for AppError {
+    fn from(error: ebay2atom::Error) -> Self {
+        Self::Page(error)
+    }
+}
+
+impl From<ProfileError> for AppError {
+    fn from(error: ProfileError) -> Self {
+        Self::Profile(error)
+    }
+}
+
+impl From<FilterError> for AppError {
+    fn from(error: FilterError) -> Self {
+        Self::Filter(error)
+    }
+}
+
+impl From<render::Error> for AppError {
+    fn from(error: render::Error) -> Self {
+        Self::Feed(error)
+    }
+}
+
+fn main() -> ExitCode {
+    // Get arguments, usage errors have their own exit code
+    let args = match Args::try_parse() {
+        Ok(args) => args,
+        Err(error) => {
+            let _ = error.print();
+            return if error.use_stderr() {
+                ExitCode::from(10)
+            } else {
+                ExitCode::SUCCESS
+            };
+        }
     };
 
-    feed.write_with_config(io::stdout(), write_config)?;
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{NAME}: {error}");
+            error.exit_code()
+        }
+    }
+}
+
+fn run(args: Args) -> Result<(), AppError> {
+    // Get profile
+    let profile = match &args.profile {
+        Some(name) => Profile::load(name)?,
+        None => Profile::default(),
+    };
+
+    // Check selectors
+    if args.check {
+        let html = match &args.command {
+            Some(Command::Fetch(fetch_args)) => {
+                Fetcher::new(&fetch_args.config())?.get(&fetch_args.url)?
+            }
+            _ => read_stdin()?,
+        };
+
+        let report = health::check_page(&html, &profile);
+        print!("{report}");
+        return if report.drift {
+            Err(AppError::Drift)
+        } else {
+            Ok(())
+        };
+    }
+
+    // Get filter
+    let filter = args.filter.filter()?;
+    let keep = |item: &SearchItem| args.keep(item, &filter);
+
+    // Get page
+    let page = match &args.command {
+        Some(Command::Fetch(fetch_args)) => fetch_pages(fetch_args, &profile, &keep)?,
+        Some(Command::Profile) => {
+            print!("{}", profile.to_toml());
+            return Ok(());
+        }
+        None => {
+            let mut page = parse_search_page_with(&read_stdin()?, &profile)?;
+            page.retain(keep);
+            page
+        }
+    };
+
+    // Report skipped items
+    for skipped in &page.skipped {
+        eprintln!("{NAME}: {skipped}");
+    }
+
+    if page.is_drifted() {
+        eprintln!("{NAME}: warning: {DRIFT_WARNING}");
+    }
+
+    // Get state
+    let now = Utc::now();
+    let mut store = open_state(&args, &id::feed_id(&page.url));
+
+    // Build feed
+    let mut feed = Feed::new(&page, now, store.as_mut());
+
+    if let Sort::Ending = args.sort {
+        feed.sort_by_end();
+    }
+
+    if let Some(mut store) = store {
+        if let Err(error) = store.save(now) {
+            let path = store.path().display();
+            eprintln!("{NAME}: cannot save state to {path}: {error}");
+        }
+    }
+
+    // Write feed
+    let stdout = io::stdout().lock();
+
+    match args.format {
+        Format::Atom => atom::write(&feed, stdout)?,
+        Format::Rss => rss::write(&feed, stdout)?,
+        Format::JsonFeed => json_feed::write(&feed, stdout)?,
+        Format::Ndjson => ndjson::write(&feed, stdout)?,
+        Format::Csv => csv::write(&feed, stdout)?,
+    }
+
     Ok(())
 }
+
+fn read_stdin() -> io::Result<String> {
+    let mut html = String::new();
+    io::stdin().read_to_string(&mut html)?;
+    Ok(html)
+}
+
+/// Download the search and follow its pagination up to the limits, keeping
+/// the listings for which `keep` returns `true`.
+///
+/// Errors on the following pages are reported, the listings collected so far
+/// are still returned.
+fn fetch_pages(
+    args: &FetchArgs,
+    profile: &Profile,
+    keep: &dyn Fn(&SearchItem) -> bool,
+) -> Result<SearchPage, AppError> {
+    let fetcher = Fetcher::new(&args.config())?;
+    let mut page = parse_search_page_with(&fetcher.get(&args.url)?, profile)?;
+    page.retain(keep);
+    let max_items = args.max_items.unwrap_or(usize::MAX);
+
+    for number in 2..=args.pages {
+        if page.items.len() >= max_items {
+            break;
+        }
+
+        let Some(url) = page.pages.get(&number).cloned() else {
+            break;
+        };
+
+        match fetcher.get(&url) {
+            Ok(html) => match parse_search_page_with(&html, profile) {
+                Ok(mut next_page) => {
+                    next_page.retain(keep);
+                    page.merge(next_page);
+                }
+                Err(error) => {
+                    eprintln!("{NAME}: invalid search page {number}: {error}");
+                    break;
+                }
+            },
+            Err(error) => {
+                eprintln!("{NAME}: download of page {number} failed: {error}");
+                break;
+            }
+        }
+    }
+
+    page.items.truncate(max_items);
+
+    if let Err(error) = fetcher.save_cookies() {
+        eprintln!("{NAME}: {error}");
+    }
+
+    Ok(page)
+}
+
+/// Open the state store, state errors are reported but never fatal.
+fn open_state(args: &Args, feed_id: &str) -> Option<StateStore> {
+    if args.no_state {
+        return None;
+    }
+
+    let path = args
+        .state
+        .clone()
+        .or_else(|| state::default_path(feed_id))?;
+
+    match StateStore::open(&path) {
+        Ok(store) => Some(store),
+        Err(error) => {
+            eprintln!("{NAME}: cannot read state from {}: {error}", path.display());
+            None
+        }
+    }
+}
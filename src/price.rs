@@ -0,0 +1,209 @@
+//! Structured prices.
+
+use core::{cmp::Ordering, fmt};
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::Site;
+
+/// Currency markers and the ISO 4217 code they stand for.
+///
+/// Markers are tried in order, so the more specific ones come first.
+const CURRENCIES: &[(&str, &str)] = &[
+    ("US $", "USD"),
+    ("C $", "CAD"),
+    ("AU $", "AUD"),
+    ("USD", "USD"),
+    ("EUR", "EUR"),
+    ("GBP", "GBP"),
+    ("CHF", "CHF"),
+    ("CAD", "CAD"),
+    ("AUD", "AUD"),
+    ("PLN", "PLN"),
+    ("zł", "PLN"),
+    ("£", "GBP"),
+    ("€", "EUR"),
+    ("¥", "JPY"),
+    ("$", "USD"),
+];
+
+static AMOUNT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\d(?:[\d.,'\s\u{a0}\u{202f}]*\d)?").unwrap());
+
+/// An amount of money in a given currency.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Money {
+    /// The amount in hundredths of the currency unit.
+    pub cents: i64,
+    /// The ISO 4217 currency code.
+    pub currency: String,
+}
+
+impl Money {
+    pub fn new(cents: i64, currency: &str) -> Self {
+        Self {
+            cents,
+            currency: currency.to_owned(),
+        }
+    }
+
+    /// The amount in currency units.
+    pub fn amount(&self) -> f64 {
+        self.cents as f64 / 100.0
+    }
+
+    /// Add `other`, `None` if it is in another currency or if the sum
+    /// overflows.
+    pub fn checked_add(&self, other: &Money) -> Option<Money> {
+        if self.currency != other.currency {
+            return None;
+        }
+
+        Some(Money {
+            cents: self.cents.checked_add(other.cents)?,
+            currency: self.currency.clone(),
+        })
+    }
+}
+
+impl PartialOrd for Money {
+    /// Amounts in different currencies are not comparable.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.currency == other.currency).then(|| self.cents.cmp(&other.cents))
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.cents < 0 { "-" } else { "" };
+        let cents = self.cents.unsigned_abs();
+        write!(
+            f,
+            "{sign}{}.{:02} {}",
+            cents / 100,
+            cents % 100,
+            self.currency
+        )
+    }
+}
+
+/// The price of a listing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Price {
+    /// The price as shown on the page.
+    pub raw: String,
+    /// The price, or the lowest price of a multi-variation listing. `None` if
+    /// the text could not be parsed.
+    pub min: Option<Money>,
+    /// The highest price of a multi-variation listing.
+    pub max: Option<Money>,
+}
+
+impl Price {
+    /// Parse a price such as `$33.00`, `EUR 1.234,56` or `$10.00 to $25.99`.
+    ///
+    /// The currency and the decimal separator are guessed from the text.
+    pub fn parse(raw: &str) -> Self {
+        Self::parse_amounts(raw, currency(raw), None)
+    }
+
+    /// Parse a price as shown on `site`.
+    ///
+    /// Prices without a currency marker are in the site currency, and amounts
+    /// in the site currency use its decimal separator.
+    pub fn parse_on(raw: &str, site: Site) -> Self {
+        let currency = currency(raw).unwrap_or(site.currency());
+        let separator = (currency == site.currency()).then(|| site.decimal_separator());
+        Self::parse_amounts(raw, Some(currency), separator)
+    }
+
+    fn parse_amounts(raw: &str, currency: Option<&str>, separator: Option<char>) -> Self {
+        // An amount too large to parse makes the whole price unknown
+        let amounts: Vec<_> = AMOUNT_REGEX
+            .find_iter(raw)
+            .map(|amount| parse_cents(amount.as_str(), separator))
+            .collect::<Option<_>>()
+            .unwrap_or_default();
+        let mut amounts = amounts.into_iter();
+
+        let money = |cents| Some(Money::new(cents, currency?));
+
+        Self {
+            raw: raw.to_owned(),
+            min: amounts.next().and_then(money),
+            max: amounts.last().and_then(money),
+        }
+    }
+
+    /// Whether the price is a range of multi-variation prices.
+    pub fn is_range(&self) -> bool {
+        self.max.is_some()
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+/// Get the ISO 4217 code of the currency used in `text`.
+pub fn currency(text: &str) -> Option<&'static str> {
+    CURRENCIES
+        .iter()
+        .find(|(marker, _)| text.contains(marker))
+        .map(|&(_, code)| code)
+}
+
+/// Get the ISO 4217 code of a currency `marker` such as `€` or `EUR`, which
+/// must be a known marker exactly.
+pub fn currency_code(marker: &str) -> Option<&'static str> {
+    CURRENCIES
+        .iter()
+        .find(|&&(known, _)| known == marker)
+        .map(|&(_, code)| code)
+}
+
+/// Parse a formatted amount into hundredths, with the given decimal
+/// separator or guessing it. `None` if the amount is too large.
+///
+/// When guessing and both `.` and `,` appear the last one is the decimal
+/// separator, otherwise a single separator followed by one or two digits is.
+/// A known separator is only taken as such when followed by one or two
+/// digits, any other `.` or `,` groups thousands.
+fn parse_cents(amount: &str, separator: Option<char>) -> Option<i64> {
+    let digits: String = amount
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '\'')
+        .collect();
+
+    let separator = match separator {
+        Some(separator) => digits
+            .rfind(separator)
+            .filter(|index| digits.len() - index - 1 <= 2),
+        None => guess_separator(&digits),
+    };
+
+    let (units, fraction) = match separator {
+        Some(index) => (&digits[..index], &digits[index + 1..]),
+        None => (digits.as_str(), ""),
+    };
+
+    let units: i64 = units.replace(['.', ','], "").parse().ok()?;
+    let fraction: i64 = format!("{fraction:0<2}")[..2].parse().ok()?;
+    units.checked_mul(100)?.checked_add(fraction)
+}
+
+/// Guess the position of the decimal separator in `digits`.
+fn guess_separator(digits: &str) -> Option<usize> {
+    match (digits.rfind('.'), digits.rfind(',')) {
+        (Some(dot), Some(comma)) => Some(dot.max(comma)),
+        (Some(index), None) | (None, Some(index)) => {
+            let decimals = digits.len() - index - 1;
+            let unique = digits.matches(&digits[index..=index]).count() == 1;
+            (unique && decimals <= 2).then_some(index)
+        }
+        (None, None) => None,
+    }
+}
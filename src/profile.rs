@@ -0,0 +1,337 @@
+//! Selector profiles, telling where each field is found in the page markup.
+//!
+//! The built-in profile holds the selectors compiled into the crate, a TOML
+//! profile only needs to list the values it overrides:
+//!
+//! ```toml
+//! [classic]
+//! price = ".s-item__price, .s-item__detail--price"
+//! ```
+
+use core::fmt;
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+use scraper::Selector;
+use serde::{Deserialize, Serialize};
+use toml::Table;
+
+use crate::{
+    Layout, AD_QUERY, BASE_URL_REGEX, BIDS_QUERY, CARD_AD_QUERY, CARD_BIDS_QUERY,
+    CARD_CONDITION_QUERY, CARD_IMAGE_QUERY, CARD_ITEMS_QUERY, CARD_LINK_QUERY, CARD_PRICE_QUERY,
+    CARD_PURCHASE_OPTIONS_QUERY, CARD_RESULTS_QUERY, CARD_SECTIONS_QUERY, CARD_SELLER_QUERY,
+    CARD_SHIPPING_QUERY, CARD_SPONSORED_QUERY, CARD_TIME_LEFT_QUERY, CARD_TITLE_QUERY,
+    CONDITION_QUERY, FEED_TITLE_QUERY, IMAGE_QUERY, ITEMS_QUERY, ITEM_ID_REGEX, ITEM_URL_REGEX,
+    LINK_QUERY, PAGE_NUMBER_REGEX, PAGINATION_QUERY, PRICE_QUERY, PURCHASE_OPTIONS_QUERY,
+    RESULTS_QUERY, SECTIONS_QUERY, SELLER_QUERY, SHIPPING_QUERY, SPONSORED_QUERY, TIME_LEFT_QUERY,
+    TITLE_QUERY,
+};
+
+/// The selectors and regexes used to extract a search page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// The search box holding the keywords.
+    pub search_title: String,
+    /// Captures the search URL from the page data.
+    pub base_url_regex: String,
+    /// Matches the item URL in the listing link.
+    pub item_url_regex: String,
+    /// Captures the item number from the item URL.
+    pub item_id_regex: String,
+    /// The links to other result pages.
+    pub pagination: String,
+    /// Captures the page number from a pagination link.
+    pub page_number_regex: String,
+    pub classic: Queries,
+    pub card: Queries,
+}
+
+/// The selectors of a layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Queries {
+    pub results: String,
+    pub items: String,
+    /// The headings starting a section of the results, such as the results
+    /// matching fewer words.
+    pub sections: String,
+    pub title: String,
+    pub link: String,
+    pub price: String,
+    pub shipping: String,
+    pub condition: String,
+    pub time_left: String,
+    pub purchase_options: String,
+    pub ad: String,
+    pub bids: String,
+    pub seller: String,
+    pub image: String,
+    /// The elements holding the sponsored label.
+    pub sponsored: String,
+}
+
+/// A profile loading error.
+#[derive(Debug)]
+pub enum ProfileError {
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    Toml {
+        path: PathBuf,
+        error: toml::de::Error,
+    },
+    /// A selector or regex does not compile, or a regex lacks its capture
+    /// group.
+    Invalid {
+        field: String,
+        message: String,
+    },
+    /// A profile name was given but no configuration directory is known.
+    NoConfigDirectory,
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, error } => write!(f, "cannot read {}: {error}", path.display()),
+            Self::Toml { path, error } => write!(f, "invalid profile {}: {error}", path.display()),
+            Self::Invalid { field, message } => write!(f, "invalid {field}: {message}"),
+            Self::NoConfigDirectory => write!(f, "no configuration directory found"),
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {}
+
+impl Default for Profile {
+    /// The built-in profile.
+    fn default() -> Self {
+        Self {
+            search_title: FEED_TITLE_QUERY.to_owned(),
+            base_url_regex: BASE_URL_REGEX.to_owned(),
+            item_url_regex: ITEM_URL_REGEX.to_owned(),
+            item_id_regex: ITEM_ID_REGEX.to_owned(),
+            pagination: PAGINATION_QUERY.to_owned(),
+            page_number_regex: PAGE_NUMBER_REGEX.to_owned(),
+            classic: Queries {
+                results: RESULTS_QUERY.to_owned(),
+                items: ITEMS_QUERY.to_owned(),
+                sections: SECTIONS_QUERY.to_owned(),
+                title: TITLE_QUERY.to_owned(),
+                link: LINK_QUERY.to_owned(),
+                price: PRICE_QUERY.to_owned(),
+                shipping: SHIPPING_QUERY.to_owned(),
+                condition: CONDITION_QUERY.to_owned(),
+                time_left: TIME_LEFT_QUERY.to_owned(),
+                purchase_options: PURCHASE_OPTIONS_QUERY.to_owned(),
+                ad: AD_QUERY.to_owned(),
+                bids: BIDS_QUERY.to_owned(),
+                seller: SELLER_QUERY.to_owned(),
+                image: IMAGE_QUERY.to_owned(),
+                sponsored: SPONSORED_QUERY.to_owned(),
+            },
+            card: Queries {
+                results: CARD_RESULTS_QUERY.to_owned(),
+                items: CARD_ITEMS_QUERY.to_owned(),
+                sections: CARD_SECTIONS_QUERY.to_owned(),
+                title: CARD_TITLE_QUERY.to_owned(),
+                link: CARD_LINK_QUERY.to_owned(),
+                price: CARD_PRICE_QUERY.to_owned(),
+                shipping: CARD_SHIPPING_QUERY.to_owned(),
+                condition: CARD_CONDITION_QUERY.to_owned(),
+                time_left: CARD_TIME_LEFT_QUERY.to_owned(),
+                purchase_options: CARD_PURCHASE_OPTIONS_QUERY.to_owned(),
+                ad: CARD_AD_QUERY.to_owned(),
+                bids: CARD_BIDS_QUERY.to_owned(),
+                seller: CARD_SELLER_QUERY.to_owned(),
+                image: CARD_IMAGE_QUERY.to_owned(),
+                sponsored: CARD_SPONSORED_QUERY.to_owned(),
+            },
+        }
+    }
+}
+
+impl Profile {
+    /// Load a profile by name or path.
+    ///
+    /// A name such as `ebay.de` refers to `ebay.de.toml` in the `profiles`
+    /// directory of the configuration, anything that looks like a path is
+    /// read as is.
+    pub fn load(name: &str) -> Result<Self, ProfileError> {
+        let path = if name.contains(std::path::MAIN_SEPARATOR) || name.ends_with(".toml") {
+            PathBuf::from(name)
+        } else {
+            profiles_directory()
+                .ok_or(ProfileError::NoConfigDirectory)?
+                .join(format!("{name}.toml"))
+        };
+
+        Self::read(&path)
+    }
+
+    /// Read the profile at `path`.
+    pub fn read(path: &Path) -> Result<Self, ProfileError> {
+        let text = fs::read_to_string(path).map_err(|error| ProfileError::Io {
+            path: path.to_owned(),
+            error,
+        })?;
+
+        let toml_error = |error| ProfileError::Toml {
+            path: path.to_owned(),
+            error,
+        };
+
+        let overrides: Table = toml::from_str(&text).map_err(toml_error)?;
+        let mut table = Table::try_from(Self::default()).expect("profile is a table");
+        merge(&mut table, overrides);
+
+        let profile: Self = table.try_into().map_err(toml_error)?;
+        profile.validate()?;
+        Ok(profile)
+    }
+
+    /// Render the profile as TOML.
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("profile is serializable")
+    }
+
+    /// Check that every selector and regex compiles, and that the regexes
+    /// meant to capture a value have a capture group.
+    pub fn validate(&self) -> Result<(), ProfileError> {
+        let capturing_regexes = [
+            ("base_url_regex", &self.base_url_regex),
+            ("item_id_regex", &self.item_id_regex),
+            ("page_number_regex", &self.page_number_regex),
+        ];
+
+        for (field, regex) in capturing_regexes {
+            compile_capturing_regex(field, regex)?;
+        }
+
+        compile_regex("item_url_regex", &self.item_url_regex)?;
+
+        compile_selector("search_title", &self.search_title)?;
+        compile_selector("pagination", &self.pagination)?;
+
+        for (layout, queries) in [("classic", &self.classic), ("card", &self.card)] {
+            for (field, query) in queries.fields() {
+                compile_selector(&format!("{layout}.{field}"), query)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the selectors of `layout`.
+    pub fn queries(&self, layout: Layout) -> &Queries {
+        match layout {
+            Layout::Classic => &self.classic,
+            Layout::Card => &self.card,
+        }
+    }
+}
+
+impl Queries {
+    /// The selectors along with their names.
+    pub fn fields(&self) -> [(&'static str, &str); 15] {
+        [
+            ("results", &self.results),
+            ("items", &self.items),
+            ("sections", &self.sections),
+            ("title", &self.title),
+            ("link", &self.link),
+            ("price", &self.price),
+            ("shipping", &self.shipping),
+            ("condition", &self.condition),
+            ("time_left", &self.time_left),
+            ("purchase_options", &self.purchase_options),
+            ("ad", &self.ad),
+            ("bids", &self.bids),
+            ("seller", &self.seller),
+            ("image", &self.image),
+            ("sponsored", &self.sponsored),
+        ]
+    }
+
+    /// The selectors looked up within each item, along with their names.
+    pub fn item_fields(&self) -> [(&'static str, &str); 12] {
+        [
+            ("title", &self.title),
+            ("link", &self.link),
+            ("price", &self.price),
+            ("shipping", &self.shipping),
+            ("condition", &self.condition),
+            ("time_left", &self.time_left),
+            ("purchase_options", &self.purchase_options),
+            ("ad", &self.ad),
+            ("bids", &self.bids),
+            ("seller", &self.seller),
+            ("image", &self.image),
+            ("sponsored", &self.sponsored),
+        ]
+    }
+}
+
+pub(crate) fn compile_selector(field: &str, query: &str) -> Result<Selector, ProfileError> {
+    Selector::parse(query).map_err(|_| ProfileError::Invalid {
+        field: field.to_owned(),
+        message: format!("invalid CSS selector {query:?}"),
+    })
+}
+
+pub(crate) fn compile_regex(field: &str, regex: &str) -> Result<Regex, ProfileError> {
+    Regex::new(regex).map_err(|error| ProfileError::Invalid {
+        field: field.to_owned(),
+        message: error.to_string(),
+    })
+}
+
+/// Compile a regex whose first capture group holds the value to extract.
+pub(crate) fn compile_capturing_regex(field: &str, regex: &str) -> Result<Regex, ProfileError> {
+    let compiled = compile_regex(field, regex)?;
+
+    if compiled.captures_len() < 2 {
+        return Err(ProfileError::Invalid {
+            field: field.to_owned(),
+            message: format!("regex {regex:?} has no capture group"),
+        });
+    }
+
+    Ok(compiled)
+}
+
+/// Get the directory of the named profiles, `$XDG_CONFIG_HOME/ebay2atom/profiles`
+/// falling back to `~/.config/ebay2atom/profiles`.
+pub fn profiles_directory() -> Option<PathBuf> {
+    Some(config_directory()?.join("profiles"))
+}
+
+/// Get the configuration directory, `$XDG_CONFIG_HOME/ebay2atom` falling back
+/// to `~/.config/ebay2atom`.
+pub(crate) fn config_directory() -> Option<PathBuf> {
+    let directory = match env::var_os("XDG_CONFIG_HOME").filter(|path| !path.is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+
+    Some(directory.join(env!("CARGO_PKG_NAME")))
+}
+
+/// Recursively merge `overrides` into `table`.
+fn merge(table: &mut Table, overrides: Table) {
+    for (key, value) in overrides {
+        match (table.get_mut(&key), value) {
+            (Some(toml::Value::Table(table)), toml::Value::Table(overrides)) => {
+                merge(table, overrides);
+            }
+            (_, value) => {
+                table.insert(key, value);
+            }
+        }
+    }
+}
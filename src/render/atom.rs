@@ -0,0 +1,120 @@
+//! Atom 1.0 output.
+
+use std::{collections::BTreeMap, io::Write};
+
+use atom_syndication::{
+    extension::ExtensionBuilder, Content, Entry, FeedBuilder, GeneratorBuilder, LinkBuilder,
+    PersonBuilder, TextBuilder, TextType, WriteConfig,
+};
+use chrono::Local;
+
+use super::{description, Error, Feed, MEDIA_NAMESPACE, NAME, REPOSITORY, VERSION};
+use crate::image;
+
+/// Write `feed` as an Atom document.
+pub fn write<W: Write>(feed: &Feed, writer: W) -> Result<(), Error> {
+    // Get generator
+    let generator = GeneratorBuilder::default()
+        .uri(Some(REPOSITORY.to_owned()))
+        .version(Some(VERSION.to_owned()))
+        .value(NAME.to_owned())
+        .build();
+
+    // Get links
+    let feed_link = LinkBuilder::default()
+        .rel("alternate".to_owned())
+        .mime_type(Some("text/html".to_owned()))
+        .href(feed.page.url.clone())
+        .build();
+
+    // Get title
+    let feed_title = TextBuilder::default()
+        .r#type(TextType::Text)
+        .value(feed.page.title.clone())
+        .build();
+
+    // Build entries
+    let entries = feed.entries.iter().map(build_entry).collect::<Vec<_>>();
+
+    // Build feed
+    let atom_feed = FeedBuilder::default()
+        .generator(Some(generator))
+        .id(feed.id.clone())
+        .links(vec![feed_link])
+        .title(feed_title)
+        .updated(feed.updated.with_timezone(&Local))
+        .namespaces(BTreeMap::from([(
+            "media".to_owned(),
+            MEDIA_NAMESPACE.to_owned(),
+        )]))
+        .entries(entries)
+        .build();
+
+    let write_config = WriteConfig {
+        write_document_declaration: true,
+        indent_size: Some(2),
+    };
+
+    atom_feed.write_with_config(writer, write_config)?;
+    Ok(())
+}
+
+fn build_entry(feed_entry: &super::Entry) -> Entry {
+    let item = feed_entry.item;
+    let mut entry = Entry::default();
+    entry.set_title(item.title.as_str());
+
+    // Get item link/id
+    let link = LinkBuilder::default()
+        .rel("alternate".to_owned())
+        .mime_type(Some("text/html".to_owned()))
+        .href(item.url.clone())
+        .build();
+
+    // Get thumbnail
+    let mut links = vec![link];
+
+    if let Some(image) = &item.image {
+        let enclosure = LinkBuilder::default()
+            .rel("enclosure".to_owned())
+            .mime_type(image::mime_type(image).map(str::to_owned))
+            .href(image.clone())
+            .build();
+
+        let thumbnail = ExtensionBuilder::default()
+            .name("media:thumbnail".to_owned())
+            .attrs(BTreeMap::from([("url".to_owned(), image.clone())]))
+            .build();
+
+        links.push(enclosure);
+        entry.set_extensions(BTreeMap::from([(
+            "media".to_owned(),
+            BTreeMap::from([("thumbnail".to_owned(), vec![thumbnail])]),
+        )]));
+    }
+
+    entry.set_links(links);
+    entry.set_id(feed_entry.id.as_str());
+
+    // Get author
+    if let Some(seller) = &item.seller {
+        let author = PersonBuilder::default().name(seller.name.clone()).build();
+        entry.set_authors([author]);
+    }
+
+    // Get content
+    let mut content = Content::default();
+    content.set_content_type(Some("xhtml".to_owned()));
+    content.set_value(format!(
+        r#"<div xmlns="http://www.w3.org/1999/xhtml">{}</div>"#,
+        description(feed_entry)
+    ));
+
+    entry.set_content(content);
+
+    // Get dates
+    let published = feed_entry.published.map(|date| date.with_timezone(&Local));
+    entry.set_published(published.map(Into::into));
+    entry.set_updated(feed_entry.updated.with_timezone(&Local));
+    entry
+}
@@ -0,0 +1,21 @@
+//! CSV output, one listing per row.
+
+use std::io::Write;
+
+use super::{Error, Feed, Record};
+
+/// Write the listings of `feed` as CSV, with a header row.
+pub fn write<W: Write>(feed: &Feed, writer: W) -> Result<(), Error> {
+    // Write the header even without listings
+    let mut writer = ::csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(writer);
+    writer.write_record(Record::FIELDS)?;
+
+    for entry in &feed.entries {
+        writer.serialize(Record::new(entry))?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
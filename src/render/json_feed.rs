@@ -0,0 +1,74 @@
+//! JSON Feed 1.1 output.
+
+use std::io::Write;
+
+use chrono::SecondsFormat;
+use serde::Serialize;
+
+use super::{description, Error, Feed};
+
+const VERSION: &str = "https://jsonfeed.org/version/1.1";
+
+#[derive(Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    home_page_url: &'a str,
+    items: Vec<JsonItem<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonItem<'a> {
+    id: &'a str,
+    url: &'a str,
+    title: &'a str,
+    content_html: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<&'a str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    authors: Vec<JsonAuthor<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_published: Option<String>,
+    date_modified: String,
+}
+
+#[derive(Serialize)]
+struct JsonAuthor<'a> {
+    name: &'a str,
+}
+
+/// Write `feed` as a JSON Feed document.
+pub fn write<W: Write>(feed: &Feed, mut writer: W) -> Result<(), Error> {
+    let items = feed
+        .entries
+        .iter()
+        .map(|entry| JsonItem {
+            id: &entry.id,
+            url: &entry.item.url,
+            title: &entry.item.title,
+            content_html: description(entry),
+            image: entry.item.image.as_deref(),
+            authors: entry
+                .item
+                .seller
+                .iter()
+                .map(|seller| JsonAuthor { name: &seller.name })
+                .collect(),
+            date_published: entry
+                .published
+                .map(|date| date.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            date_modified: entry.updated.to_rfc3339_opts(SecondsFormat::Secs, true),
+        })
+        .collect();
+
+    let json_feed = JsonFeed {
+        version: VERSION,
+        title: &feed.page.title,
+        home_page_url: &feed.page.url,
+        items,
+    };
+
+    serde_json::to_writer_pretty(&mut writer, &json_feed)?;
+    writeln!(writer)?;
+    Ok(())
+}
@@ -0,0 +1,246 @@
+//! Rendering of search pages into feeds and exports.
+//!
+//! A [`Feed`] holds the dated entries of a search page, each submodule writes
+//! it in a different format.
+
+pub mod atom;
+pub mod csv;
+pub mod json_feed;
+pub mod ndjson;
+mod record;
+pub mod rss;
+
+use core::fmt;
+use std::io;
+
+use chrono::{DateTime, Duration, Local, Utc};
+
+use crate::{id, state::StateStore, xml::escape, SearchItem, SearchPage, Section};
+
+pub use record::Record;
+
+// Manifest environment variables
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+const REPOSITORY: &str = env!("CARGO_PKG_REPOSITORY");
+const NAME: &str = env!("CARGO_PKG_NAME");
+
+/// The Media RSS namespace, for thumbnails.
+const MEDIA_NAMESPACE: &str = "http://search.yahoo.com/mrss/";
+
+/// A search page ready to be rendered.
+#[derive(Debug, Clone)]
+pub struct Feed<'a> {
+    pub page: &'a SearchPage,
+    pub id: String,
+    /// The most recent entry update.
+    pub updated: DateTime<Utc>,
+    pub entries: Vec<Entry<'a>>,
+}
+
+/// An item ready to be rendered.
+#[derive(Debug, Clone)]
+pub struct Entry<'a> {
+    pub item: &'a SearchItem,
+    pub id: String,
+    /// When the item was first seen, if known.
+    pub published: Option<DateTime<Utc>>,
+    pub updated: DateTime<Utc>,
+    /// When the auction ends, if the time left is known.
+    pub ends: Option<DateTime<Utc>>,
+}
+
+impl<'a> Feed<'a> {
+    /// Date the items of `page` using `store`, without a store every item is
+    /// updated `now`.
+    pub fn new(
+        page: &'a SearchPage,
+        now: DateTime<Utc>,
+        mut store: Option<&mut StateStore>,
+    ) -> Self {
+        let entries: Vec<_> = page
+            .items
+            .iter()
+            .map(|item| {
+                let (published, updated) = match store.as_deref_mut() {
+                    Some(store) => {
+                        let state = store.observe(item, now);
+                        (Some(state.first_seen), state.updated)
+                    }
+                    None => (None, now),
+                };
+
+                Entry {
+                    item,
+                    id: id::entry_id(&item.id),
+                    published,
+                    updated,
+                    ends: item.ends_in.and_then(|ends_in| end_time(now, ends_in)),
+                }
+            })
+            .collect();
+
+        let updated = entries.iter().map(|entry| entry.updated).max();
+
+        Self {
+            page,
+            id: id::feed_id(&page.url),
+            updated: updated.unwrap_or(now),
+            entries,
+        }
+    }
+}
+
+impl Feed<'_> {
+    /// Sort the entries by end time, the soonest first and the entries
+    /// without an end time last.
+    pub fn sort_by_end(&mut self) {
+        self.entries
+            .sort_by_key(|entry| (entry.ends.is_none(), entry.ends));
+    }
+}
+
+/// Get the end time of an auction ending in `ends_in`, rounded to the minute
+/// so that it does not change on each refresh, or `None` when it cannot be
+/// represented.
+fn end_time(now: DateTime<Utc>, ends_in: Duration) -> Option<DateTime<Utc>> {
+    let ends = now.checked_add_signed(ends_in)?;
+    ends.checked_sub_signed(
+        Duration::seconds(ends.timestamp().rem_euclid(60))
+            + Duration::nanoseconds(ends.timestamp_subsec_nanos().into()),
+    )
+}
+
+/// A rendering error.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Atom(::atom_syndication::Error),
+    Rss(::rss::Error),
+    Json(serde_json::Error),
+    Csv(::csv::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => error.fmt(f),
+            Self::Atom(error) => error.fmt(f),
+            Self::Rss(error) => error.fmt(f),
+            Self::Json(error) => error.fmt(f),
+            Self::Csv(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<::atom_syndication::Error> for Error {
+    fn from(error: ::atom_syndication::Error) -> Self {
+        Self::Atom(error)
+    }
+}
+
+impl From<::rss::Error> for Error {
+    fn from(error: ::rss::Error) -> Self {
+        Self::Rss(error)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+impl From<::csv::Error> for Error {
+    fn from(error: ::csv::Error) -> Self {
+        Self::Csv(error)
+    }
+}
+
+/// Get the HTML description of an entry, shared by all the formats.
+pub fn description(entry: &Entry) -> String {
+    let item = entry.item;
+    let mut description = String::new();
+
+    if let Some(image) = &item.image {
+        description += &format!(
+            r#"<p><img src="{}" alt="{}"/></p>"#,
+            escape(image),
+            escape(&item.title)
+        );
+    }
+
+    description += &format!("<p>Price: {}</p>", escape(&item.price.raw));
+
+    if let Some(shipping) = &item.shipping {
+        description += &format!("<p>Shipping: {}</p>", escape(&shipping.raw));
+    }
+
+    if let Some(total) = item.total() {
+        description += &format!("<p>Total: {total}</p>");
+    }
+
+    match (item.canonical_condition, &item.condition) {
+        (Some(canonical), Some(condition))
+            if !condition.eq_ignore_ascii_case(canonical.label()) =>
+        {
+            let label = canonical.label();
+            description += &format!("<p>Condition: {label} ({})</p>", escape(condition));
+        }
+        (Some(canonical), _) => description += &format!("<p>Condition: {}</p>", canonical.label()),
+        (None, Some(condition)) => {
+            description += &format!("<p>Condition: {}</p>", escape(condition));
+        }
+        (None, None) => {}
+    }
+
+    if let Some(ends) = entry.ends {
+        let ends = ends.with_timezone(&Local).format("%Y-%m-%d %H:%M");
+        description += &format!("<p>Ends: {ends}</p>");
+    } else if let Some(time_left) = &item.time_left {
+        description += &format!("<p>Time left: {}</p>", escape(time_left));
+    }
+
+    // The raw purchase options and ad label are only shown when they tell
+    // nothing known
+    if item.buying.format.is_some() {
+        description += &format!("<p>Format: {}</p>", item.buying);
+    } else {
+        if let Some(purchase_options) = &item.purchase_options {
+            description += &format!("<p>Purchase options: {}</p>", escape(purchase_options));
+        }
+
+        if let Some(ad) = &item.ad {
+            description += &format!("<p>Ad: {}</p>", escape(ad));
+        }
+    }
+
+    if let Some(seller) = &item.seller {
+        description += &format!("<p>Seller: {}</p>", escape(&seller.to_string()));
+    }
+
+    if item.new_listing {
+        description += "<p>New listing</p>";
+    }
+
+    if item.sponsored {
+        description += "<p>Sponsored listing</p>";
+    }
+
+    match item.section {
+        Section::Exact => {}
+        Section::FewerWords => description += "<p>Section: results matching fewer words</p>",
+        Section::International => {
+            description += "<p>Section: results from international sellers</p>";
+        }
+    }
+
+    description
+}
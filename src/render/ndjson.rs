@@ -0,0 +1,15 @@
+//! Newline-delimited JSON output, one listing per line.
+
+use std::io::Write;
+
+use super::{Error, Feed, Record};
+
+/// Write the listings of `feed` as JSON lines.
+pub fn write<W: Write>(feed: &Feed, mut writer: W) -> Result<(), Error> {
+    for entry in &feed.entries {
+        serde_json::to_writer(&mut writer, &Record::new(entry))?;
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use serde::Serialize;
+
+use crate::{BuyingFormat, Condition, Seller, ShippingCost};
+
+use super::Entry;
+
+/// A flat record of the entry fields, for tabular and line-based formats.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Record<'a> {
+    pub id: &'a str,
+    pub title: &'a str,
+    pub new_listing: bool,
+    pub url: &'a str,
+    pub section: &'static str,
+    /// The price as shown on the page.
+    pub price: &'a str,
+    pub price_min: Option<f64>,
+    pub price_max: Option<f64>,
+    pub currency: Option<&'a str>,
+    /// The shipping as shown on the page.
+    pub shipping: Option<&'a str>,
+    /// The shipping cost, zero for free shipping and local pickup.
+    pub shipping_cost: Option<f64>,
+    /// The lowest price with shipping.
+    pub total: Option<f64>,
+    pub condition: Option<&'a str>,
+    pub canonical_condition: Option<&'static str>,
+    pub time_left: Option<&'a str>,
+    /// The end time in RFC 3339 format.
+    pub ends: Option<String>,
+    pub purchase_options: Option<&'a str>,
+    pub ad: Option<&'a str>,
+    pub seller: Option<&'a str>,
+    pub seller_feedback: Option<u32>,
+    /// The share of positive feedback, in percent.
+    pub seller_positive: Option<f64>,
+    pub image: Option<&'a str>,
+    pub buying_format: Option<&'static str>,
+    pub bids: Option<u32>,
+    pub accepts_offers: bool,
+    pub sponsored: bool,
+}
+
+impl<'a> Record<'a> {
+    /// The field names, in order, as used for the CSV header.
+    pub const FIELDS: [&'static str; 26] = [
+        "id",
+        "title",
+        "new_listing",
+        "url",
+        "section",
+        "price",
+        "price_min",
+        "price_max",
+        "currency",
+        "shipping",
+        "shipping_cost",
+        "total",
+        "condition",
+        "canonical_condition",
+        "time_left",
+        "ends",
+        "purchase_options",
+        "ad",
+        "seller",
+        "seller_feedback",
+        "seller_positive",
+        "image",
+        "buying_format",
+        "bids",
+        "accepts_offers",
+        "sponsored",
+    ];
+
+    pub fn new(entry: &'a Entry) -> Self {
+        let item = entry.item;
+        let min = item.price.min.as_ref();
+        let max = item.price.max.as_ref();
+        let shipping = item.shipping.as_ref();
+        let seller = item.seller.as_ref();
+
+        Self {
+            id: &item.id,
+            title: &item.title,
+            new_listing: item.new_listing,
+            url: &item.url,
+            section: item.section.name(),
+            price: &item.price.raw,
+            price_min: min.map(|money| money.amount()),
+            price_max: max.map(|money| money.amount()),
+            currency: min.map(|money| money.currency.as_str()),
+            shipping: shipping.map(|shipping| shipping.raw.as_str()),
+            shipping_cost: shipping.and_then(|shipping| match &shipping.cost {
+                ShippingCost::Free | ShippingCost::LocalPickup => Some(0.0),
+                ShippingCost::Paid(cost) => Some(cost.amount()),
+                ShippingCost::NotSpecified => None,
+            }),
+            total: item.total().map(|total| total.amount()),
+            condition: item.condition.as_deref(),
+            canonical_condition: item.canonical_condition.map(Condition::name),
+            time_left: item.time_left.as_deref(),
+            ends: entry.ends.map(|ends| ends.to_rfc3339()),
+            purchase_options: item.purchase_options.as_deref(),
+            ad: item.ad.as_deref(),
+            seller: seller.map(|seller| seller.name.as_str()),
+            seller_feedback: seller.and_then(|seller| seller.feedback),
+            seller_positive: seller.and_then(Seller::positive),
+            image: item.image.as_deref(),
+            buying_format: item.buying.format.map(BuyingFormat::name),
+            bids: item.buying.bids,
+            accepts_offers: item.buying.accepts_offers,
+            sponsored: item.sponsored,
+        }
+    }
+}
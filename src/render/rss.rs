@@ -0,0 +1,72 @@
+//! RSS 2.0 output.
+
+use std::{collections::BTreeMap, io::Write};
+
+use rss::{
+    extension::{dublincore::DublinCoreExtensionBuilder, ExtensionBuilder},
+    ChannelBuilder, GuidBuilder, Item, ItemBuilder,
+};
+
+use super::{description, Error, Feed, MEDIA_NAMESPACE, NAME, VERSION};
+
+/// Write `feed` as an RSS document.
+pub fn write<W: Write>(feed: &Feed, writer: W) -> Result<(), Error> {
+    let items = feed.entries.iter().map(build_item).collect::<Vec<_>>();
+
+    let channel = ChannelBuilder::default()
+        .title(feed.page.title.clone())
+        .link(feed.page.url.clone())
+        .description(format!("eBay search for \"{}\"", feed.page.title))
+        .generator(Some(format!("{NAME} {VERSION}")))
+        .last_build_date(Some(feed.updated.to_rfc2822()))
+        .namespaces(BTreeMap::from([(
+            "media".to_owned(),
+            MEDIA_NAMESPACE.to_owned(),
+        )]))
+        .items(items)
+        .build();
+
+    channel.pretty_write_to(writer, b' ', 2)?;
+    Ok(())
+}
+
+fn build_item(entry: &super::Entry) -> Item {
+    let item = entry.item;
+
+    // The guid is not the item URL, which changes with the eBay domain
+    let guid = GuidBuilder::default()
+        .value(entry.id.clone())
+        .permalink(false)
+        .build();
+
+    let date = entry.published.unwrap_or(entry.updated);
+
+    // The RSS author must be an email address, the seller goes to dc:creator
+    let creator = item.seller.as_ref().map(|seller| {
+        DublinCoreExtensionBuilder::default()
+            .creators(vec![seller.name.clone()])
+            .build()
+    });
+
+    let thumbnail = item.image.as_ref().map(|image| {
+        let thumbnail = ExtensionBuilder::default()
+            .name("media:thumbnail".to_owned())
+            .attrs(BTreeMap::from([("url".to_owned(), image.clone())]))
+            .build();
+
+        BTreeMap::from([(
+            "media".to_owned(),
+            BTreeMap::from([("thumbnail".to_owned(), vec![thumbnail])]),
+        )])
+    });
+
+    ItemBuilder::default()
+        .title(Some(item.title.clone()))
+        .link(Some(item.url.clone()))
+        .description(Some(description(entry)))
+        .guid(Some(guid))
+        .pub_date(Some(date.to_rfc2822()))
+        .dublin_core_ext(creator)
+        .extensions(thumbnail.unwrap_or_default())
+        .build()
+}
@@ -0,0 +1,201 @@
+//! Splitting of rule expressions into tokens.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::{Op, RuleError};
+
+/// The kind of a token, along with its value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum TokenKind {
+    /// A field, a keyword or a bare value such as `used` or `EUR`.
+    Word(String),
+    /// A number such as `500` or `12.50`.
+    Number(String),
+    /// A duration such as `2h` or `1d4h`, in seconds.
+    Duration(i64),
+    /// A quoted string.
+    Text(String),
+    /// A regex literal such as `/rtx ?30[89]0/i`.
+    Regex {
+        pattern: String,
+        flags: String,
+    },
+    /// A currency symbol such as `€`.
+    Currency(char),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    LeftParen,
+    RightParen,
+    End,
+}
+
+/// A token and its byte range in the rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Token {
+    pub kind: TokenKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Currency symbols allowed before or after an amount.
+const CURRENCY_SYMBOLS: &[char] = &['$', '€', '£', '¥'];
+
+/// Duration units and their length in seconds.
+const DURATION_UNITS: &[(&str, i64)] = &[("d", 86_400), ("h", 3_600), ("m", 60), ("s", 1)];
+
+static DURATION_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:\d+[dhms])+\b").unwrap());
+static DURATION_PART_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d+)([dhms])").unwrap());
+static NUMBER_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\d+(?:\.\d+)?").unwrap());
+
+/// Split `source` into tokens, ending with [`TokenKind::End`].
+pub(super) fn tokenize(source: &str) -> Result<Vec<Token>, RuleError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let error = |message: String| RuleError::new(source, start, message);
+        let rest = &source[start..];
+
+        let (kind, length) = if c.is_ascii_digit() {
+            number(rest)
+        } else if c.is_alphabetic() || c == '_' {
+            let length = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+                .unwrap_or(rest.len());
+            let word = &rest[..length];
+
+            let kind = match word.to_lowercase().as_str() {
+                "and" => TokenKind::And,
+                "or" => TokenKind::Or,
+                "not" => TokenKind::Not,
+                _ => TokenKind::Word(word.to_owned()),
+            };
+            (kind, length)
+        } else if c == '"' || c == '\'' {
+            let (text, length) = quoted(rest, c)
+                .ok_or_else(|| error("unterminated string, add the closing quote".to_owned()))?;
+            (TokenKind::Text(text), length)
+        } else if c == '/' {
+            let (pattern, length) = quoted(rest, '/')
+                .ok_or_else(|| error("unterminated regex, add the closing /".to_owned()))?;
+            let flags_length = rest[length..]
+                .find(|c: char| !c.is_alphabetic())
+                .unwrap_or(rest.len() - length);
+            let flags = rest[length..length + flags_length].to_owned();
+
+            if let Some(flag) = flags.chars().find(|flag| !"imsx".contains(*flag)) {
+                return Err(RuleError::new(
+                    source,
+                    start + length,
+                    format!("unknown regex flag {flag:?}, expected i, m, s or x"),
+                ));
+            }
+
+            (TokenKind::Regex { pattern, flags }, length + flags_length)
+        } else if CURRENCY_SYMBOLS.contains(&c) {
+            (TokenKind::Currency(c), c.len_utf8())
+        } else if c == '(' {
+            (TokenKind::LeftParen, 1)
+        } else if c == ')' {
+            (TokenKind::RightParen, 1)
+        } else if let Some((op, length)) = operator(rest) {
+            (TokenKind::Op(op), length)
+        } else if c == '=' {
+            return Err(error("use == to compare values".to_owned()));
+        } else {
+            return Err(error(format!("unexpected character {c:?}")));
+        };
+
+        let end = start + length;
+        tokens.push(Token { kind, start, end });
+
+        while chars.peek().is_some_and(|&(index, _)| index < end) {
+            chars.next();
+        }
+    }
+
+    tokens.push(Token {
+        kind: TokenKind::End,
+        start: source.len(),
+        end: source.len(),
+    });
+
+    Ok(tokens)
+}
+
+/// Read a number, or a duration when the digits are directly followed by a
+/// unit.
+fn number(text: &str) -> (TokenKind, usize) {
+    if let Some(duration) = DURATION_REGEX.find(text) {
+        let seconds = DURATION_PART_REGEX
+            .captures_iter(duration.as_str())
+            .map(|captures| {
+                let amount: i64 = captures[1].parse().unwrap_or(i64::MAX);
+                let unit = DURATION_UNITS
+                    .iter()
+                    .find(|(name, _)| *name == &captures[2])
+                    .map_or(1, |&(_, seconds)| seconds);
+                amount.saturating_mul(unit)
+            })
+            .fold(0_i64, i64::saturating_add);
+
+        return (TokenKind::Duration(seconds), duration.end());
+    }
+
+    let number = NUMBER_REGEX.find(text).map_or("", |number| number.as_str());
+    (TokenKind::Number(number.to_owned()), number.len())
+}
+
+/// Read a string delimited by `quote`, with `\` escaping the quote and
+/// itself. Other escapes are kept as is, as they belong to regexes.
+fn quoted(text: &str, quote: char) -> Option<(String, usize)> {
+    let mut value = String::new();
+    let mut chars = text.char_indices().skip(1);
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                (_, escaped) if escaped == quote || (escaped == '\\' && quote != '/') => {
+                    value.push(escaped);
+                }
+                (_, escaped) => {
+                    value.push('\\');
+                    value.push(escaped);
+                }
+            },
+            c if c == quote => return Some((value, index + 1)),
+            c => value.push(c),
+        }
+    }
+
+    None
+}
+
+fn operator(text: &str) -> Option<(Op, usize)> {
+    const OPERATORS: &[(&str, Op)] = &[
+        ("==", Op::Eq),
+        ("!=", Op::Ne),
+        ("!~", Op::NotMatches),
+        ("<=", Op::Le),
+        (">=", Op::Ge),
+        ("<", Op::Lt),
+        (">", Op::Gt),
+        ("~", Op::Matches),
+    ];
+
+    OPERATORS
+        .iter()
+        .find(|(symbol, _)| text.starts_with(symbol))
+        .map(|&(symbol, op)| (op, symbol.len()))
+}
@@ -0,0 +1,423 @@
+//! A small expression language to filter listings on their parsed fields.
+//!
+//! ```text
+//! title ~ /rtx ?30[89]0/i and total < 500 EUR and not condition == "for parts"
+//! ```
+//!
+//! A rule compares fields with values and combines the comparisons with
+//! `and`, `or`, `not` and parentheses. A comparison on a missing value, such
+//! as the bids of a fixed price listing, is false.
+
+mod lexer;
+mod parser;
+
+use core::{cmp::Ordering, fmt, str::FromStr};
+
+use chrono::Duration;
+use regex::Regex;
+
+use crate::{filter::Limit, BuyingFormat, Condition, Money, SearchItem, Section, ShippingCost};
+
+/// A compiled rule.
+#[derive(Debug, Clone)]
+pub struct Rule {
+    source: String,
+    expr: Expr,
+}
+
+/// An error in a rule, at a given position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleError {
+    /// The rule.
+    pub source: String,
+    /// The byte offset of the error in the rule.
+    pub position: usize,
+    pub message: String,
+}
+
+/// Whether a rule matched a listing, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub matched: bool,
+    /// The comparisons that decided the outcome, with the compared values.
+    pub reasons: Vec<String>,
+}
+
+/// The fields a rule can compare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Field {
+    Price,
+    Shipping,
+    Total,
+    Condition,
+    Bids,
+    TimeLeft,
+    Seller,
+    Title,
+    Section,
+    Format,
+}
+
+/// A comparison operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// Matches a regex, or contains a string regardless of case.
+    Matches,
+    NotMatches,
+}
+
+/// A value compared with a field.
+#[derive(Debug, Clone)]
+pub(crate) enum Value {
+    Amount(Limit),
+    Count(u32),
+    Duration(Duration),
+    Text(String),
+    Pattern(Regex),
+    Condition(Condition),
+    Section(Section),
+    Format(BuyingFormat),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare {
+        field: Field,
+        op: Op,
+        value: Value,
+        /// The comparison as written in the rule.
+        text: String,
+    },
+}
+
+/// The value of a field for a given listing.
+enum Actual<'a> {
+    Amount(Money),
+    Count(u32),
+    Duration(Duration),
+    Text(&'a str),
+    Condition(Condition),
+    Section(Section),
+    Format(BuyingFormat),
+}
+
+impl Rule {
+    /// The rule as written.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether `item` matches the rule.
+    pub fn matches(&self, item: &SearchItem) -> bool {
+        self.evaluate(item).matched
+    }
+
+    /// Evaluate the rule against `item`, keeping the comparisons that decided
+    /// the outcome.
+    pub fn evaluate(&self, item: &SearchItem) -> Outcome {
+        self.expr.evaluate(item)
+    }
+}
+
+impl FromStr for Rule {
+    type Err = RuleError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let tokens = lexer::tokenize(source)?;
+        let expr = parser::parse(source, &tokens)?;
+
+        Ok(Self {
+            source: source.to_owned(),
+            expr,
+        })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl RuleError {
+    pub(crate) fn new(source: &str, position: usize, message: String) -> Self {
+        Self {
+            source: source.to_owned(),
+            position,
+            message,
+        }
+    }
+
+    /// The 1-based column of the error, in characters.
+    pub fn column(&self) -> usize {
+        self.source[..self.position].chars().count() + 1
+    }
+}
+
+impl fmt::Display for RuleError {
+    /// Write the message, then the rule with a caret under the error.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let column = self.column();
+        writeln!(f, "{} at column {column}", self.message)?;
+        writeln!(f, "    {}", self.source)?;
+        write!(f, "    {:>column$}", "^")
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.reasons.join(", "))
+    }
+}
+
+impl Field {
+    pub(crate) const ALL: [Self; 10] = [
+        Self::Price,
+        Self::Shipping,
+        Self::Total,
+        Self::Condition,
+        Self::Bids,
+        Self::TimeLeft,
+        Self::Seller,
+        Self::Title,
+        Self::Section,
+        Self::Format,
+    ];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Price => "price",
+            Self::Shipping => "shipping",
+            Self::Total => "total",
+            Self::Condition => "condition",
+            Self::Bids => "bids",
+            Self::TimeLeft => "time_left",
+            Self::Seller => "seller",
+            Self::Title => "title",
+            Self::Section => "section",
+            Self::Format => "format",
+        }
+    }
+
+    /// The operators the field can be compared with.
+    pub(crate) fn ops(self) -> &'static [Op] {
+        match self {
+            Self::Price | Self::Shipping | Self::Total | Self::Bids | Self::TimeLeft => {
+                &[Op::Eq, Op::Ne, Op::Lt, Op::Le, Op::Gt, Op::Ge]
+            }
+            Self::Seller | Self::Title => &[Op::Eq, Op::Ne, Op::Matches, Op::NotMatches],
+            Self::Condition | Self::Section | Self::Format => &[Op::Eq, Op::Ne],
+        }
+    }
+
+    fn actual(self, item: &SearchItem) -> Option<Actual<'_>> {
+        match self {
+            Self::Price => item.price.min.clone().map(Actual::Amount),
+            Self::Shipping => {
+                let currency = &item.price.min.as_ref()?.currency;
+
+                match &item.shipping.as_ref()?.cost {
+                    ShippingCost::Free | ShippingCost::LocalPickup => {
+                        Some(Actual::Amount(Money::new(0, currency)))
+                    }
+                    ShippingCost::Paid(cost) => Some(Actual::Amount(cost.clone())),
+                    ShippingCost::NotSpecified => None,
+                }
+            }
+            Self::Total => item.total().map(Actual::Amount),
+            Self::Condition => item.canonical_condition.map(Actual::Condition),
+            Self::Bids => item.buying.bids.map(Actual::Count),
+            Self::TimeLeft => item.ends_in.map(Actual::Duration),
+            Self::Seller => Some(Actual::Text(&item.seller.as_ref()?.name)),
+            Self::Title => Some(Actual::Text(&item.title)),
+            Self::Section => Some(Actual::Section(item.section)),
+            Self::Format => item.buying.format.map(Actual::Format),
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Op {
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Matches => "~",
+            Self::NotMatches => "!~",
+        }
+    }
+
+    /// Whether the operator holds for a field ordered as `ordering` relative
+    /// to the value.
+    fn orders(self, ordering: Ordering) -> bool {
+        match self {
+            Self::Eq => ordering.is_eq(),
+            Self::Ne => ordering.is_ne(),
+            Self::Lt => ordering.is_lt(),
+            Self::Le => ordering.is_le(),
+            Self::Gt => ordering.is_gt(),
+            Self::Ge => ordering.is_ge(),
+            Self::Matches | Self::NotMatches => false,
+        }
+    }
+
+    /// Whether the operator holds for a field equal, or matching, the value
+    /// when `equal` is true.
+    fn equates(self, equal: bool) -> bool {
+        match self {
+            Self::Eq | Self::Matches => equal,
+            Self::Ne | Self::NotMatches => !equal,
+            Self::Lt | Self::Le | Self::Gt | Self::Ge => false,
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+impl Expr {
+    fn evaluate(&self, item: &SearchItem) -> Outcome {
+        match self {
+            Self::And(left, right) => {
+                let left = left.evaluate(item);
+                if !left.matched {
+                    return left;
+                }
+
+                let right = right.evaluate(item);
+                if !right.matched {
+                    return right;
+                }
+
+                Outcome {
+                    matched: true,
+                    reasons: [left.reasons, right.reasons].concat(),
+                }
+            }
+            Self::Or(left, right) => {
+                let left = left.evaluate(item);
+                if left.matched {
+                    return left;
+                }
+
+                let right = right.evaluate(item);
+                if right.matched {
+                    return right;
+                }
+
+                Outcome {
+                    matched: false,
+                    reasons: [left.reasons, right.reasons].concat(),
+                }
+            }
+            Self::Not(expr) => {
+                let outcome = expr.evaluate(item);
+                Outcome {
+                    matched: !outcome.matched,
+                    reasons: outcome.reasons,
+                }
+            }
+            Self::Compare {
+                field,
+                op,
+                value,
+                text,
+            } => {
+                let actual = field.actual(item);
+                let matched = actual
+                    .as_ref()
+                    .is_some_and(|actual| compare(actual, *op, value));
+                let actual = actual.map_or("unknown".to_owned(), |actual| actual.to_string());
+
+                Outcome {
+                    matched,
+                    reasons: vec![format!("{text} is {matched} ({field} is {actual})")],
+                }
+            }
+        }
+    }
+}
+
+/// Whether `actual` compared with `value` satisfies `op`.
+///
+/// Amounts in different currencies never do, texts are compared regardless
+/// of case.
+fn compare(actual: &Actual, op: Op, value: &Value) -> bool {
+    match (actual, value) {
+        (Actual::Amount(money), Value::Amount(limit)) => {
+            limit.accepts(&money.currency) && op.orders(money.cents.cmp(&limit.cents))
+        }
+        (Actual::Count(count), Value::Count(value)) => op.orders(count.cmp(value)),
+        (Actual::Duration(duration), Value::Duration(value)) => op.orders(duration.cmp(value)),
+        (Actual::Text(text), Value::Text(value)) => {
+            let text = text.to_lowercase();
+            let value = value.to_lowercase();
+
+            match op {
+                Op::Matches | Op::NotMatches => op.equates(text.contains(&value)),
+                _ => op.equates(text == value),
+            }
+        }
+        (Actual::Text(text), Value::Pattern(regex)) => op.equates(regex.is_match(text)),
+        (Actual::Condition(condition), Value::Condition(value)) => op.equates(condition == value),
+        (Actual::Section(section), Value::Section(value)) => op.equates(section == value),
+        (Actual::Format(format), Value::Format(value)) => op.equates(format == value),
+        _ => false,
+    }
+}
+
+impl fmt::Display for Actual<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Amount(money) => write!(f, "{money}"),
+            Self::Count(count) => write!(f, "{count}"),
+            Self::Duration(duration) => write_duration(f, *duration),
+            Self::Text(text) => write!(f, "{text:?}"),
+            Self::Condition(condition) => write!(f, "{condition}"),
+            Self::Section(section) => write!(f, "{section}"),
+            Self::Format(format) => write!(f, "{format}"),
+        }
+    }
+}
+
+/// Write a duration as days, hours, minutes and seconds, e.g. `2d 4h`.
+fn write_duration(f: &mut fmt::Formatter<'_>, duration: Duration) -> fmt::Result {
+    let mut seconds = duration.num_seconds();
+    let mut parts = Vec::new();
+
+    for (unit, length) in [("d", 86_400), ("h", 3_600), ("m", 60), ("s", 1)] {
+        if seconds >= length {
+            parts.push(format!("{}{unit}", seconds / length));
+            seconds %= length;
+        }
+    }
+
+    if parts.is_empty() {
+        f.write_str("0s")
+    } else {
+        f.write_str(&parts.join(" "))
+    }
+}
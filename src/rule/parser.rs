@@ -0,0 +1,395 @@
+//! Parsing of rule tokens into an expression, checking that each field is
+//! compared with a value of its type.
+//!
+//! ```text
+//! or         = and ("or" and)*
+//! and        = not ("and" not)*
+//! not        = "not" not | "(" or ")" | comparison
+//! comparison = field operator value
+//! ```
+
+use chrono::Duration;
+use regex::Regex;
+
+use super::{
+    lexer::{Token, TokenKind},
+    Expr, Field, Op, RuleError, Value,
+};
+use crate::{filter::Limit, price, time_left, BuyingFormat, Condition, Section};
+
+/// Parse the tokens of `source` into an expression.
+pub(super) fn parse(source: &str, tokens: &[Token]) -> Result<Expr, RuleError> {
+    let mut parser = Parser {
+        source,
+        tokens,
+        index: 0,
+    };
+
+    if parser.peek().kind == TokenKind::End {
+        return Err(parser.error(parser.peek(), "empty rule".to_owned()));
+    }
+
+    let expr = parser.or()?;
+    let token = parser.peek();
+
+    if token.kind != TokenKind::End {
+        let message = format!(
+            "expected \"and\", \"or\" or the end of the rule, found {}",
+            describe(&token.kind)
+        );
+        return Err(parser.error(token, message));
+    }
+
+    Ok(expr)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: &'a [Token],
+    index: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> &'a Token {
+        &self.tokens[self.index]
+    }
+
+    fn next(&mut self) -> &'a Token {
+        let token = &self.tokens[self.index];
+        self.index = (self.index + 1).min(self.tokens.len() - 1);
+        token
+    }
+
+    fn error(&self, token: &Token, message: String) -> RuleError {
+        RuleError::new(self.source, token.start, message)
+    }
+
+    fn or(&mut self) -> Result<Expr, RuleError> {
+        let mut expr = self.and()?;
+
+        while self.peek().kind == TokenKind::Or {
+            self.next();
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, RuleError> {
+        let mut expr = self.not()?;
+
+        while self.peek().kind == TokenKind::And {
+            self.next();
+            expr = Expr::And(Box::new(expr), Box::new(self.not()?));
+        }
+
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> Result<Expr, RuleError> {
+        let token = self.next();
+
+        match &token.kind {
+            TokenKind::Not => Ok(Expr::Not(Box::new(self.not()?))),
+            TokenKind::LeftParen => {
+                let expr = self.or()?;
+                let close = self.next();
+
+                match close.kind {
+                    TokenKind::RightParen => {}
+                    TokenKind::End => {
+                        let message = "unclosed \"(\", add the closing \")\"".to_owned();
+                        return Err(self.error(token, message));
+                    }
+                    _ => return Err(self.expected(close, "\")\"", &close.kind)),
+                }
+
+                Ok(expr)
+            }
+            TokenKind::Word(name) => self.comparison(token, name),
+            kind => {
+                let message = format!(
+                    "expected a field such as title or price, found {}",
+                    describe(kind)
+                );
+                Err(self.error(token, message))
+            }
+        }
+    }
+
+    /// Parse the comparison starting with the field `name`.
+    fn comparison(&mut self, field_token: &Token, name: &str) -> Result<Expr, RuleError> {
+        let field = Field::ALL
+            .into_iter()
+            .find(|field| field.name() == name.to_lowercase())
+            .ok_or_else(|| {
+                let message = match suggest(name) {
+                    Some(field) => format!("unknown field {name:?}, did you mean {field:?}?"),
+                    None => format!(
+                        "unknown field {name:?}, expected {}",
+                        one_of(Field::ALL.iter().map(|field| field.name()))
+                    ),
+                };
+                self.error(field_token, message)
+            })?;
+
+        let op_token = self.next();
+        let TokenKind::Op(op) = op_token.kind else {
+            let message = format!(
+                "expected an operator such as == or < after {field}, found {}",
+                describe(&op_token.kind)
+            );
+            return Err(self.error(op_token, message));
+        };
+
+        if !field.ops().contains(&op) {
+            let message = format!(
+                "{op} cannot compare {field}, use {}",
+                one_of(field.ops().iter().map(|op| op.symbol()))
+            );
+            return Err(self.error(op_token, message));
+        }
+
+        let value = self.value(field, op)?;
+        let end = self.tokens[self.index - 1].end;
+
+        Ok(Expr::Compare {
+            field,
+            op,
+            value,
+            text: self.source[field_token.start..end].to_owned(),
+        })
+    }
+
+    /// Parse a value of the type of `field`.
+    fn value(&mut self, field: Field, op: Op) -> Result<Value, RuleError> {
+        match field {
+            Field::Price | Field::Shipping | Field::Total => self.amount().map(Value::Amount),
+            Field::Bids => {
+                let token = self.next();
+                match &token.kind {
+                    TokenKind::Number(number) => number.parse().map(Value::Count).map_err(|_| {
+                        self.error(token, "expected a whole number of bids".to_owned())
+                    }),
+                    kind => Err(self.expected(token, "a number of bids such as 3", kind)),
+                }
+            }
+            Field::TimeLeft => self.duration().map(Value::Duration),
+            Field::Seller | Field::Title => {
+                let token = self.next();
+                match &token.kind {
+                    TokenKind::Text(text) | TokenKind::Word(text) => Ok(Value::Text(text.clone())),
+                    TokenKind::Regex { .. } if matches!(op, Op::Eq | Op::Ne) => {
+                        let message = format!("use ~ or !~ to match a regex, not {op}");
+                        Err(self.error(token, message))
+                    }
+                    TokenKind::Regex { pattern, flags } => self.regex(token, pattern, flags),
+                    kind => Err(self.expected(token, "a string or a regex", kind)),
+                }
+            }
+            Field::Condition => self
+                .name(
+                    Condition::ALL.iter().map(|condition| condition.name()),
+                    "condition",
+                )
+                .map(|name| Value::Condition(name.parse().expect("known condition"))),
+            Field::Section => self
+                .name(Section::ALL.iter().map(|section| section.name()), "section")
+                .map(|name| Value::Section(name.parse().expect("known section"))),
+            Field::Format => self
+                .name(
+                    BuyingFormat::ALL.iter().map(|format| format.name()),
+                    "format",
+                )
+                .map(|name| Value::Format(name.parse().expect("known format"))),
+        }
+    }
+
+    /// Parse an amount such as `500`, `12.50 EUR` or `€12.50`.
+    fn amount(&mut self) -> Result<Limit, RuleError> {
+        const EXPECTED: &str = "an amount such as 500 or 12.50 EUR";
+
+        let prefix = match self.peek().kind {
+            TokenKind::Currency(symbol) => {
+                self.next();
+                Some(symbol)
+            }
+            _ => None,
+        };
+
+        let token = self.next();
+        let TokenKind::Number(number) = &token.kind else {
+            return Err(self.expected(token, EXPECTED, &token.kind));
+        };
+
+        let mut limit: Limit = number
+            .parse()
+            .map_err(|_| self.error(token, "amounts have at most two decimals".to_owned()))?;
+
+        let currency = match (prefix, &self.peek().kind) {
+            (None, TokenKind::Currency(symbol)) => {
+                self.next();
+                Some(symbol.to_string())
+            }
+            (None, TokenKind::Word(code)) => {
+                let token = self.next();
+                let code = code.to_uppercase();
+
+                if price::currency(&code) != Some(code.as_str()) {
+                    let message = format!("unknown currency {code:?}, expected e.g. EUR or USD");
+                    return Err(self.error(token, message));
+                }
+
+                Some(code)
+            }
+            (prefix, _) => prefix.map(String::from),
+        };
+
+        limit.currency = currency.map(|currency| {
+            price::currency(&currency)
+                .expect("known currency")
+                .to_owned()
+        });
+        Ok(limit)
+    }
+
+    /// Parse a duration such as `2h`, `1d4h` or `1d 4h`.
+    fn duration(&mut self) -> Result<Duration, RuleError> {
+        let token = self.next();
+        let TokenKind::Duration(mut seconds) = token.kind else {
+            let expected = match token.kind {
+                TokenKind::Number(_) => "a duration with a unit, such as 2h or 30m",
+                _ => "a duration such as 2h or 1d 4h",
+            };
+            return Err(self.expected(token, expected, &token.kind));
+        };
+
+        while let TokenKind::Duration(more) = self.peek().kind {
+            self.next();
+            seconds = seconds.saturating_add(more);
+        }
+
+        // Times left are always below the bound, so comparisons with longer
+        // durations keep their outcome once capped
+        let seconds = seconds.min(time_left::LIMIT_SECONDS);
+        Ok(Duration::seconds(seconds))
+    }
+
+    /// Parse one of `names`, written bare or quoted, ignoring case and with
+    /// spaces or underscores standing for dashes.
+    fn name(
+        &mut self,
+        names: impl Iterator<Item = &'static str> + Clone,
+        kind: &str,
+    ) -> Result<&'static str, RuleError> {
+        let token = self.next();
+        let (TokenKind::Word(text) | TokenKind::Text(text)) = &token.kind else {
+            let expected = format!("a {kind} such as {}", names.clone().next().unwrap_or(""));
+            return Err(self.expected(token, &expected, &token.kind));
+        };
+
+        let normalized = text.trim().to_lowercase().replace([' ', '_'], "-");
+        names
+            .clone()
+            .find(|name| *name == normalized)
+            .ok_or_else(|| {
+                let message = format!("unknown {kind} {text:?}, expected {}", one_of(names));
+                self.error(token, message)
+            })
+    }
+
+    fn regex(&self, token: &Token, pattern: &str, flags: &str) -> Result<Value, RuleError> {
+        let pattern = if flags.is_empty() {
+            pattern.to_owned()
+        } else {
+            format!("(?{flags}){pattern}")
+        };
+
+        Regex::new(&pattern).map(Value::Pattern).map_err(|error| {
+            let error = error.to_string();
+            let reason = error
+                .lines()
+                .last()
+                .unwrap_or_default()
+                .trim_start_matches("error: ");
+            self.error(token, format!("invalid regex: {reason}"))
+        })
+    }
+
+    fn expected(&self, token: &Token, expected: &str, found: &TokenKind) -> RuleError {
+        let message = format!("expected {expected}, found {}", describe(found));
+        self.error(token, message)
+    }
+}
+
+/// Describe a token in an error message.
+fn describe(kind: &TokenKind) -> String {
+    match kind {
+        TokenKind::Word(word) => format!("{word:?}"),
+        TokenKind::Number(number) => number.clone(),
+        TokenKind::Duration(_) => "a duration".to_owned(),
+        TokenKind::Text(text) => format!("the string {text:?}"),
+        TokenKind::Regex { .. } => "a regex".to_owned(),
+        TokenKind::Currency(symbol) => format!("{symbol:?}"),
+        TokenKind::Op(op) => format!("\"{op}\""),
+        TokenKind::And => "\"and\"".to_owned(),
+        TokenKind::Or => "\"or\"".to_owned(),
+        TokenKind::Not => "\"not\"".to_owned(),
+        TokenKind::LeftParen => "\"(\"".to_owned(),
+        TokenKind::RightParen => "\")\"".to_owned(),
+        TokenKind::End => "the end of the rule".to_owned(),
+    }
+}
+
+/// List `names` as `a, b or c`.
+fn one_of<'a>(names: impl Iterator<Item = &'a str>) -> String {
+    let names: Vec<_> = names.collect();
+
+    match names.split_last() {
+        Some((last, [])) => (*last).to_owned(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+        None => String::new(),
+    }
+}
+
+/// Get the field closest to the misspelled `name`, if any is close enough.
+fn suggest(name: &str) -> Option<&'static str> {
+    let name = name.to_lowercase().replace('-', "_");
+
+    Field::ALL
+        .iter()
+        .map(|field| (field.name(), distance(&name, field.name())))
+        .filter(|&(_, distance)| distance <= 2)
+        .min_by_key(|&(_, distance)| distance)
+        .map(|(field, _)| field)
+}
+
+/// The edit distance between `a` and `b`, counting a transposition of two
+/// adjacent characters as one edit.
+fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = rows[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            let mut cost = substitution.min(rows[i - 1][j] + 1).min(rows[i][j - 1] + 1);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cost = cost.min(rows[i - 2][j - 2] + 1);
+            }
+
+            rows[i][j] = cost;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
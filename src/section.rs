@@ -0,0 +1,60 @@
+//! The sections a search results page is split into.
+
+use core::{fmt, str::FromStr};
+
+use crate::vocabulary::{self, Vocabulary};
+
+/// The section of the results a listing was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Section {
+    /// Results matching every search keyword.
+    #[default]
+    Exact,
+    /// Results matching fewer words, listed after the exact matches.
+    FewerWords,
+    /// Results from international sellers.
+    International,
+}
+
+impl Section {
+    pub const ALL: [Self; 3] = [Self::Exact, Self::FewerWords, Self::International];
+
+    /// Get the section started by the heading `text`, if it is a known one.
+    ///
+    /// Any other heading leaves the current section unchanged.
+    pub fn from_heading(text: &str, vocabulary: &Vocabulary) -> Option<Self> {
+        if vocabulary::contains(text, vocabulary.fewer_words) {
+            Some(Self::FewerWords)
+        } else if vocabulary::contains(text, vocabulary.international) {
+            Some(Self::International)
+        } else {
+            None
+        }
+    }
+
+    /// The name of the section, as accepted by [`Section::from_str`].
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Exact => "exact",
+            Self::FewerWords => "fewer-words",
+            Self::International => "international",
+        }
+    }
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Section {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|section| section.name() == name)
+            .ok_or_else(|| format!("unknown section {name:?}"))
+    }
+}
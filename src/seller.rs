@@ -0,0 +1,79 @@
+//! Seller information.
+
+use core::fmt;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+static FEEDBACK_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\((\d+(?:[.,]\d+)*)\s*([KkMm])?\)").unwrap());
+static POSITIVE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d+(?:[.,]\d+)?)\s*%").unwrap());
+
+/// The seller of a listing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Seller {
+    /// The eBay user name.
+    pub name: String,
+    /// The number of feedback ratings.
+    pub feedback: Option<u32>,
+    /// The share of positive feedback, in tenths of a percent.
+    pub positive_permille: Option<u16>,
+}
+
+impl Seller {
+    /// Parse a seller such as `gadgetshop (1,234) 99.5%` or `gadgetshop
+    /// 99.5% positive (1.2K)`, `None` if no name is found.
+    pub fn parse(text: &str) -> Option<Self> {
+        let name = text.split_whitespace().next()?.trim_end_matches('(');
+
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let feedback = FEEDBACK_REGEX.captures(text).and_then(|captures| {
+            let multiplier = match captures.get(2).map(|unit| unit.as_str()) {
+                Some("K" | "k") => 1_000.0,
+                Some("M" | "m") => 1_000_000.0,
+                _ => return captures[1].replace(['.', ','], "").parse().ok(),
+            };
+
+            let count: f64 = captures[1].replace(',', ".").parse().ok()?;
+            Some((count * multiplier).round() as u32)
+        });
+
+        let positive_permille = POSITIVE_REGEX.captures(text).and_then(|captures| {
+            let percent: f64 = captures[1].replace(',', ".").parse().ok()?;
+            (percent <= 100.0).then(|| (percent * 10.0).round() as u16)
+        });
+
+        Some(Self {
+            name: name.to_owned(),
+            feedback,
+            positive_permille,
+        })
+    }
+
+    /// The share of positive feedback, in percent.
+    pub fn positive(&self) -> Option<f64> {
+        self.positive_permille
+            .map(|permille| f64::from(permille) / 10.0)
+    }
+}
+
+impl fmt::Display for Seller {
+    /// Write the seller with its feedback, such as `gadgetshop (1234
+    /// ratings, 99.5% positive)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+
+        match (self.feedback, self.positive()) {
+            (Some(feedback), Some(positive)) => {
+                write!(f, " ({feedback} ratings, {positive}% positive)")
+            }
+            (Some(feedback), None) => write!(f, " ({feedback} ratings)"),
+            (None, Some(positive)) => write!(f, " ({positive}% positive)"),
+            (None, None) => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Shipping costs.
+
+use core::fmt;
+
+use crate::{
+    price::{Money, Price},
+    vocabulary, Site,
+};
+
+/// The shipping offered for a listing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shipping {
+    /// The shipping as shown on the page.
+    pub raw: String,
+    pub cost: ShippingCost,
+}
+
+/// What shipping costs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShippingCost {
+    Free,
+    /// The item can only be picked up, at no cost.
+    LocalPickup,
+    Paid(Money),
+    /// The seller did not specify the shipping, or the text could not be
+    /// parsed.
+    NotSpecified,
+}
+
+impl Shipping {
+    /// Parse a shipping such as `+$5.99 shipping`, `Free shipping`, `Free
+    /// local pickup` or `Shipping not specified`, as shown on `site`.
+    ///
+    /// Local pickup is tried first, as it is usually advertised as free.
+    pub fn parse(raw: &str, site: Site) -> Self {
+        let vocabulary = site.vocabulary();
+        let has = |phrases: &[&str]| vocabulary::contains(raw, phrases);
+
+        let cost = if has(vocabulary.local_pickup) {
+            ShippingCost::LocalPickup
+        } else if has(vocabulary.free_shipping) {
+            ShippingCost::Free
+        } else if has(vocabulary.shipping_not_specified) {
+            ShippingCost::NotSpecified
+        } else {
+            Price::parse_on(raw, site)
+                .min
+                .map_or(ShippingCost::NotSpecified, ShippingCost::Paid)
+        };
+
+        Self {
+            raw: raw.to_owned(),
+            cost,
+        }
+    }
+
+    /// Get the total price of an item costing `price`, `None` when the
+    /// shipping cost is unknown or in another currency.
+    pub fn total(&self, price: &Money) -> Option<Money> {
+        match &self.cost {
+            ShippingCost::Free | ShippingCost::LocalPickup => Some(price.clone()),
+            ShippingCost::Paid(cost) => price.checked_add(cost),
+            ShippingCost::NotSpecified => None,
+        }
+    }
+}
+
+impl fmt::Display for Shipping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
@@ -0,0 +1,87 @@
+//! The supported eBay sites and their locale rules.
+
+use core::{fmt, str::FromStr};
+
+use crate::vocabulary::{self, Vocabulary};
+
+/// An eBay site, each one with its own language, currency and number
+/// format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Site {
+    /// ebay.com, also used for unknown domains.
+    #[default]
+    Com,
+    De,
+    CoUk,
+    It,
+    Fr,
+}
+
+impl Site {
+    pub const ALL: [Self; 5] = [Self::Com, Self::De, Self::CoUk, Self::It, Self::Fr];
+
+    /// Identify the site of a URL from its domain.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+        let host = url.split(['/', '?', '#']).next()?.to_lowercase();
+
+        Self::ALL.into_iter().find(|site| {
+            let domain = site.domain();
+            host == domain || host.ends_with(&format!(".{domain}"))
+        })
+    }
+
+    pub fn domain(self) -> &'static str {
+        match self {
+            Self::Com => "ebay.com",
+            Self::De => "ebay.de",
+            Self::CoUk => "ebay.co.uk",
+            Self::It => "ebay.it",
+            Self::Fr => "ebay.fr",
+        }
+    }
+
+    /// The ISO 4217 code of the currency of prices without a marker.
+    pub fn currency(self) -> &'static str {
+        match self {
+            Self::Com => "USD",
+            Self::CoUk => "GBP",
+            Self::De | Self::It | Self::Fr => "EUR",
+        }
+    }
+
+    /// The decimal separator of amounts.
+    pub fn decimal_separator(self) -> char {
+        match self {
+            Self::Com | Self::CoUk => '.',
+            Self::De | Self::It | Self::Fr => ',',
+        }
+    }
+
+    /// The labels, badges and units of the site language.
+    pub fn vocabulary(self) -> &'static Vocabulary {
+        match self {
+            Self::Com | Self::CoUk => &vocabulary::ENGLISH,
+            Self::De => &vocabulary::GERMAN,
+            Self::It => &vocabulary::ITALIAN,
+            Self::Fr => &vocabulary::FRENCH,
+        }
+    }
+}
+
+impl fmt::Display for Site {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.domain())
+    }
+}
+
+impl FromStr for Site {
+    type Err = String;
+
+    fn from_str(domain: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|site| site.domain() == domain)
+            .ok_or_else(|| format!("unknown site {domain:?}"))
+    }
+}
@@ -0,0 +1,188 @@
+//! Persistent record of when items were first seen and last changed.
+//!
+//! The state is stored as a tab-separated file with one line per item number,
+//! holding the first seen, last changed and last seen times along with a
+//! fingerprint of the visible item data.
+
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::SearchItem;
+
+/// Items that have not been seen for this many days are forgotten.
+const RETENTION_DAYS: i64 = 30;
+
+/// What is known about an item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemState {
+    pub first_seen: DateTime<Utc>,
+    /// The last time the visible item data changed.
+    pub updated: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    fingerprint: u64,
+}
+
+/// A state file, keyed by item number.
+#[derive(Debug)]
+pub struct StateStore {
+    path: PathBuf,
+    items: HashMap<String, ItemState>,
+}
+
+impl StateStore {
+    /// Load the state stored at `path`, a missing file is an empty state.
+    ///
+    /// Malformed lines are ignored.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error),
+        };
+
+        let items = text.lines().filter_map(parse_line).collect();
+        Ok(Self { path, items })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, item_number: &str) -> Option<&ItemState> {
+        self.items.get(item_number)
+    }
+
+    /// Record that `item` was seen at `now` and return its updated state.
+    pub fn observe(&mut self, item: &SearchItem, now: DateTime<Utc>) -> &ItemState {
+        let fingerprint = fingerprint(item);
+        let state = self
+            .items
+            .entry(item.id.clone())
+            .or_insert_with(|| ItemState {
+                first_seen: now,
+                updated: now,
+                last_seen: now,
+                fingerprint,
+            });
+
+        if state.fingerprint != fingerprint {
+            state.fingerprint = fingerprint;
+            state.updated = now;
+        }
+
+        state.last_seen = now;
+        state
+    }
+
+    /// Forget old items and write the state back to its file.
+    ///
+    /// The file is replaced atomically, so that concurrent readers never see
+    /// a partial state.
+    pub fn save(&mut self, now: DateTime<Utc>) -> io::Result<()> {
+        let oldest = now - Duration::days(RETENTION_DAYS);
+        self.items.retain(|_, state| state.last_seen >= oldest);
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut lines: Vec<_> = self.items.iter().collect();
+        lines.sort_by_key(|(number, _)| *number);
+
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        let mut file = io::BufWriter::new(fs::File::create(&temporary)?);
+
+        for (number, state) in lines {
+            writeln!(
+                file,
+                "{number}\t{}\t{}\t{}\t{:016x}",
+                state.first_seen.to_rfc3339(),
+                state.updated.to_rfc3339(),
+                state.last_seen.to_rfc3339(),
+                state.fingerprint
+            )?;
+        }
+
+        file.into_inner()?.sync_all()?;
+        fs::rename(temporary, &self.path)
+    }
+}
+
+/// Get the default state file for a feed.
+///
+/// Each feed gets its own file under `$XDG_STATE_HOME/ebay2atom`, falling
+/// back to `~/.local/state/ebay2atom`, so that feeds refreshed in parallel do
+/// not overwrite each other's state.
+pub fn default_path(feed_id: &str) -> Option<PathBuf> {
+    let directory = match env::var_os("XDG_STATE_HOME").filter(|path| !path.is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from(env::var_os("HOME")?).join(".local/state"),
+    };
+
+    let file = format!("{:016x}.tsv", fnv1a(feed_id.as_bytes()));
+    Some(directory.join(env!("CARGO_PKG_NAME")).join(file))
+}
+
+fn parse_line(line: &str) -> Option<(String, ItemState)> {
+    let mut fields = line.split('\t');
+    let number = fields.next()?.to_owned();
+    let mut date = || {
+        DateTime::parse_from_rfc3339(fields.next()?)
+            .ok()
+            .map(|date| date.with_timezone(&Utc))
+    };
+
+    let first_seen = date()?;
+    let updated = date()?;
+    let last_seen = date()?;
+    let fingerprint = u64::from_str_radix(fields.next()?, 16).ok()?;
+
+    let state = ItemState {
+        first_seen,
+        updated,
+        last_seen,
+        fingerprint,
+    };
+
+    Some((number, state))
+}
+
+/// Hash the data of an item that is visible in its entry.
+///
+/// The time left is left out, as it changes on every refresh.
+fn fingerprint(item: &SearchItem) -> u64 {
+    let bids = item.buying.bids.map(|bids| bids.to_string());
+    let fields = [
+        Some(item.title.as_str()),
+        Some(item.price.raw.as_str()),
+        item.shipping.as_ref().map(|shipping| shipping.raw.as_str()),
+        item.condition.as_deref(),
+        item.purchase_options.as_deref(),
+        item.ad.as_deref(),
+        bids.as_deref(),
+    ];
+
+    let mut data = Vec::new();
+
+    for field in fields {
+        data.extend_from_slice(field.unwrap_or_default().as_bytes());
+        data.push(0);
+    }
+
+    fnv1a(&data)
+}
+
+/// The 64-bit FNV-1a hash, which unlike the standard hasher is stable.
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
@@ -0,0 +1,44 @@
+//! Parsing of the time left before an auction ends.
+
+use std::sync::LazyLock;
+
+use chrono::Duration;
+use regex::Regex;
+
+use crate::vocabulary::Vocabulary;
+
+static AMOUNT_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\d+)\s*(\p{L}+)").unwrap());
+
+/// The bound of a plausible time left, in seconds. Listings run for a few
+/// weeks at most, longer times are rejected as bogus.
+pub const LIMIT_SECONDS: i64 = 365 * 86_400;
+
+/// Parse a time left such as `2d 4h left`, `3h 12m`, `Noch 2T 4Std.` or
+/// `2j 4h` with the units of `vocabulary`.
+///
+/// Numbers followed by an unknown word are ignored, `None` is returned when
+/// no amount of time is found at all or when it reaches [`LIMIT_SECONDS`].
+pub fn parse(text: &str, vocabulary: &Vocabulary) -> Option<Duration> {
+    let mut seconds = None;
+
+    for captures in AMOUNT_REGEX.captures_iter(text) {
+        let unit = captures[2].to_lowercase();
+        let Some(&(_, length)) = vocabulary
+            .time_units
+            .iter()
+            .find(|(names, _)| names.contains(&unit.as_str()))
+        else {
+            continue;
+        };
+
+        let amount: i64 = captures[1].parse().ok()?;
+        let total = amount
+            .checked_mul(length)?
+            .checked_add(seconds.unwrap_or(0))?;
+        seconds = Some(total);
+    }
+
+    seconds
+        .filter(|&seconds| seconds < LIMIT_SECONDS)
+        .map(Duration::seconds)
+}
@@ -0,0 +1,92 @@
+//! Listing titles, cleaned of the badges eBay mixes into them.
+
+use crate::vocabulary::Vocabulary;
+
+/// The title of a listing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Title {
+    /// The title, without badges and with normalized whitespace.
+    pub text: String,
+    /// Whether the title carried a "New Listing" badge.
+    pub new_listing: bool,
+}
+
+impl Title {
+    /// Clean the text nodes of a title element.
+    ///
+    /// Nodes holding only a badge are dropped, the remaining ones are joined
+    /// with spaces, as sibling elements are separate words, and stripped of
+    /// any badge prefix or suffix.
+    pub fn parse<'a>(nodes: impl IntoIterator<Item = &'a str>, vocabulary: &Vocabulary) -> Self {
+        let mut new_listing = false;
+        let mut text = String::new();
+
+        for node in nodes {
+            match badge(node.trim(), vocabulary) {
+                Some(is_new_listing) => new_listing |= is_new_listing,
+                None => {
+                    text.push(' ');
+                    text.push_str(node);
+                }
+            }
+        }
+
+        let mut text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        while let Some((rest, is_new_listing)) = strip_badge(&text, vocabulary) {
+            new_listing |= is_new_listing;
+            text = rest;
+        }
+
+        Self { text, new_listing }
+    }
+}
+
+/// If `text` is a badge, whether it is the "New Listing" one.
+fn badge(text: &str, vocabulary: &Vocabulary) -> Option<bool> {
+    let is = |badge: &&str| badge.eq_ignore_ascii_case(text);
+
+    if vocabulary.new_listing.iter().any(is) {
+        Some(true)
+    } else if vocabulary.title_badges.iter().any(is) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Strip a badge from the start or the end of `text`.
+fn strip_badge(text: &str, vocabulary: &Vocabulary) -> Option<(String, bool)> {
+    let badges = vocabulary
+        .new_listing
+        .iter()
+        .map(|badge| (badge, true))
+        .chain(vocabulary.title_badges.iter().map(|badge| (badge, false)));
+
+    for (badge, is_new_listing) in badges {
+        if let Some(rest) = strip_prefix(text, badge).or_else(|| strip_suffix(text, badge)) {
+            return Some((rest.trim().to_owned(), is_new_listing));
+        }
+    }
+
+    None
+}
+
+/// Strip `badge` from the start of `text`, as a whole word.
+fn strip_prefix<'a>(text: &'a str, badge: &str) -> Option<&'a str> {
+    let prefix = text.get(..badge.len())?;
+    let rest = &text[badge.len()..];
+    let whole_word = !rest.starts_with(char::is_alphanumeric);
+
+    (prefix.eq_ignore_ascii_case(badge) && whole_word).then_some(rest)
+}
+
+/// Strip `badge` from the end of `text`, as a whole word.
+fn strip_suffix<'a>(text: &'a str, badge: &str) -> Option<&'a str> {
+    let start = text.len().checked_sub(badge.len())?;
+    let suffix = text.get(start..)?;
+    let rest = &text[..start];
+    let whole_word = !rest.ends_with(char::is_alphanumeric);
+
+    (suffix.eq_ignore_ascii_case(badge) && whole_word).then_some(rest)
+}
@@ -0,0 +1,176 @@
+//! The words eBay uses on its pages, in each supported language.
+//!
+//! Phrases are matched case-insensitively against lowercased text, so they
+//! are written in lowercase, except for the title badges which are matched
+//! as whole text nodes.
+
+use crate::Condition;
+
+/// The labels, badges and units of a language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Vocabulary {
+    /// The "New Listing" title badge.
+    pub new_listing: &'static [&'static str],
+    /// Other title badges and screen reader hints.
+    pub title_badges: &'static [&'static str],
+    /// The sponsored label, with whitespace removed.
+    pub sponsored: &'static [&'static str],
+    /// The heading of the results matching fewer words.
+    pub fewer_words: &'static [&'static str],
+    /// The heading of the results from international sellers.
+    pub international: &'static [&'static str],
+    /// The auction label, matched as whole words.
+    pub auction: &'static [&'static str],
+    pub buy_it_now: &'static [&'static str],
+    pub best_offer: &'static [&'static str],
+    pub classified: &'static [&'static str],
+    pub local_pickup: &'static [&'static str],
+    pub free_shipping: &'static [&'static str],
+    pub shipping_not_specified: &'static [&'static str],
+    /// The conditions, tried in order.
+    pub conditions: &'static [(Condition, &'static [&'static str])],
+    /// The time units and their length in seconds.
+    pub time_units: &'static [(&'static [&'static str], i64)],
+}
+
+pub const ENGLISH: Vocabulary = Vocabulary {
+    new_listing: &["New Listing"],
+    title_badges: &["Opens in a new window or tab", "Sponsored"],
+    sponsored: &["sponsored"],
+    fewer_words: &["fewer words"],
+    international: &["international sellers"],
+    auction: &["auction"],
+    buy_it_now: &["buy it now"],
+    best_offer: &["best offer"],
+    classified: &["classified"],
+    local_pickup: &["local pickup", "local collection", "collection in person"],
+    free_shipping: &["free shipping", "free delivery", "free postage"],
+    shipping_not_specified: &["not specified"],
+    conditions: &[
+        (
+            Condition::ForParts,
+            &["for parts", "parts only", "not working"],
+        ),
+        (
+            Condition::OpenBox,
+            &["open box", "new (other)", "new other"],
+        ),
+        (
+            Condition::Refurbished,
+            &["refurbished", "renewed", "remanufactured"],
+        ),
+        (Condition::Used, &["used", "pre-owned", "like new"]),
+        (Condition::New, &["new"]),
+    ],
+    time_units: &[
+        (&["d", "day", "days"], 86_400),
+        (&["h", "hr", "hrs", "hour", "hours"], 3_600),
+        (&["m", "min", "mins", "minute", "minutes"], 60),
+        (&["s", "sec", "secs", "second", "seconds"], 1),
+    ],
+};
+
+pub const GERMAN: Vocabulary = Vocabulary {
+    new_listing: &["Neues Angebot"],
+    title_badges: &["Wird in neuem Fenster oder Tab geöffnet", "Gesponsert"],
+    sponsored: &["gesponsert"],
+    fewer_words: &["weniger suchbegriffe", "weniger wörter"],
+    international: &["internationalen verkäufern", "internationale verkäufer"],
+    auction: &["auktion"],
+    buy_it_now: &["sofort-kaufen", "sofortkaufen"],
+    best_offer: &["preisvorschlag"],
+    classified: &["kleinanzeige"],
+    local_pickup: &["abholung"],
+    free_shipping: &["kostenloser versand", "versand kostenlos"],
+    shipping_not_specified: &["nicht angegeben"],
+    conditions: &[
+        (Condition::ForParts, &["ersatzteil", "defekt"]),
+        (Condition::OpenBox, &["neu: sonstige", "neu (sonstige)"]),
+        (Condition::Refurbished, &["überholt"]),
+        (Condition::Used, &["gebraucht", "neuwertig"]),
+        (Condition::New, &["neu"]),
+    ],
+    time_units: &[
+        (&["t", "tag", "tage"], 86_400),
+        (&["std", "stunde", "stunden"], 3_600),
+        (&["min", "minute", "minuten"], 60),
+        (&["sek", "sekunde", "sekunden"], 1),
+    ],
+};
+
+pub const ITALIAN: Vocabulary = Vocabulary {
+    new_listing: &["Nuova inserzione"],
+    title_badges: &["Si apre in una nuova finestra o scheda", "Sponsorizzato"],
+    sponsored: &["sponsorizzato"],
+    fewer_words: &["meno parole"],
+    international: &["venditori internazionali"],
+    auction: &["asta"],
+    buy_it_now: &["compralo subito"],
+    best_offer: &["proposta d'acquisto"],
+    classified: &["annuncio"],
+    local_pickup: &["ritiro"],
+    free_shipping: &["spedizione gratuita"],
+    shipping_not_specified: &["non specificat"],
+    conditions: &[
+        (Condition::ForParts, &["per parti", "non funzionante"]),
+        (Condition::OpenBox, &["nuovo: altro", "nuovo (altro)"]),
+        (Condition::Refurbished, &["ricondizionato"]),
+        (Condition::Used, &["usato"]),
+        (Condition::New, &["nuovo"]),
+    ],
+    time_units: &[
+        (&["g", "giorno", "giorni"], 86_400),
+        (&["h", "ora", "ore"], 3_600),
+        (&["m", "min", "minuto", "minuti"], 60),
+        (&["s", "sec", "secondo", "secondi"], 1),
+    ],
+};
+
+pub const FRENCH: Vocabulary = Vocabulary {
+    new_listing: &["Nouvelle annonce"],
+    title_badges: &[
+        "S'ouvre dans une nouvelle fenêtre ou un nouvel onglet",
+        "Sponsorisé",
+    ],
+    sponsored: &["sponsorisé"],
+    fewer_words: &["moins de mots"],
+    international: &["vendeurs internationaux"],
+    auction: &["enchères"],
+    buy_it_now: &["achat immédiat"],
+    best_offer: &["offre directe"],
+    classified: &["petite annonce"],
+    local_pickup: &["retrait"],
+    free_shipping: &["livraison gratuite"],
+    shipping_not_specified: &["non spécifié", "non précisé"],
+    conditions: &[
+        (Condition::ForParts, &["pour pièces", "ne fonctionne pas"]),
+        (Condition::OpenBox, &["neuf: autre", "neuf (autre)"]),
+        (Condition::Refurbished, &["reconditionné"]),
+        (Condition::Used, &["occasion"]),
+        (Condition::New, &["neuf"]),
+    ],
+    time_units: &[
+        (&["j", "jour", "jours"], 86_400),
+        (&["h", "heure", "heures"], 3_600),
+        (&["min", "minute", "minutes"], 60),
+        (&["s", "sec", "seconde", "secondes"], 1),
+    ],
+};
+
+/// Whether `text`, once lowercased, contains one of `phrases`.
+pub(crate) fn contains(text: &str, phrases: &[&str]) -> bool {
+    let text = text.to_lowercase();
+    phrases.iter().any(|phrase| text.contains(phrase))
+}
+
+/// Whether `text` contains `word` as a whole word, ignoring case.
+pub(crate) fn contains_word(text: &str, word: &str) -> bool {
+    let text = text.to_lowercase();
+    let word = word.to_lowercase();
+
+    text.match_indices(&word).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
@@ -0,0 +1,32 @@
+//! Helpers to embed scraped text in XML documents.
+
+/// Remove the characters that cannot appear in an XML document.
+///
+/// Control characters are stripped, except for tabs and line breaks, along
+/// with the `U+FFFE` and `U+FFFF` noncharacters.
+pub fn sanitize(text: &str) -> String {
+    text.chars().filter(|&c| !is_forbidden(c)).collect()
+}
+
+/// Sanitize `text` and escape it for use as XML character data or attribute
+/// value.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars().filter(|&c| !is_forbidden(c)) {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn is_forbidden(c: char) -> bool {
+    (c.is_control() && !matches!(c, '\t' | '\n' | '\r')) || matches!(c, '\u{FFFE}' | '\u{FFFF}')
+}
@@ -0,0 +1,80 @@
+use ebay2atom::{Buying, BuyingFormat, Site};
+
+#[test]
+fn classifies_localized_formats() {
+    let cases = [
+        (
+            Site::De,
+            Some("Sofort-Kaufen"),
+            None,
+            None,
+            BuyingFormat::BuyItNow,
+        ),
+        (
+            Site::De,
+            Some("oder Preisvorschlag"),
+            None,
+            None,
+            BuyingFormat::BestOffer,
+        ),
+        (
+            Site::De,
+            None,
+            None,
+            Some("12 Gebote"),
+            BuyingFormat::Auction,
+        ),
+        (
+            Site::It,
+            Some("Compralo Subito"),
+            None,
+            None,
+            BuyingFormat::BuyItNow,
+        ),
+        (Site::It, Some("Asta"), None, None, BuyingFormat::Auction),
+        (
+            // "rimasta" holds the "asta" auction label
+            Site::It,
+            Some("Compralo Subito, unica rimasta"),
+            None,
+            None,
+            BuyingFormat::BuyItNow,
+        ),
+        (
+            Site::Fr,
+            Some("Achat immédiat ou Offre directe"),
+            None,
+            None,
+            BuyingFormat::BuyItNow,
+        ),
+        (
+            Site::Com,
+            None,
+            Some("Classified Ad"),
+            None,
+            BuyingFormat::Classified,
+        ),
+    ];
+
+    for (site, purchase_options, ad, bids, format) in cases {
+        let buying = Buying::parse(purchase_options, ad, bids, site.vocabulary());
+        assert_eq!(
+            buying.format,
+            Some(format),
+            "{purchase_options:?} {ad:?} {bids:?}"
+        );
+    }
+}
+
+#[test]
+fn writes_badges() {
+    let english = Site::Com.vocabulary();
+
+    let auction = Buying::parse(Some("Buy It Now"), None, Some("1 bid"), english);
+    assert_eq!(auction.to_string(), "Auction, 1 bid");
+
+    let offers = Buying::parse(Some("Buy It Now or Best Offer"), None, None, english);
+    assert_eq!(offers.to_string(), "Buy It Now, accepts offers");
+
+    assert_eq!(Buying::parse(None, None, None, english).format, None);
+}
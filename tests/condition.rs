@@ -0,0 +1,39 @@
+use ebay2atom::{Condition, Site};
+
+#[test]
+fn maps_conditions() {
+    let cases = [
+        (Site::Com, "Brand New", Condition::New),
+        (Site::De, "Neu", Condition::New),
+        (Site::It, "Nuovo", Condition::New),
+        (Site::Fr, "Neuf", Condition::New),
+        (Site::Com, "Open Box", Condition::OpenBox),
+        (
+            Site::De,
+            "Neu: Sonstige (siehe Artikelbeschreibung)",
+            Condition::OpenBox,
+        ),
+        (
+            Site::CoUk,
+            "Certified - Refurbished",
+            Condition::Refurbished,
+        ),
+        (Site::De, "Generalüberholt", Condition::Refurbished),
+        (Site::It, "Ricondizionato", Condition::Refurbished),
+        (Site::Com, "Pre-Owned", Condition::Used),
+        (Site::CoUk, "Like New", Condition::Used),
+        (Site::De, "Gebraucht", Condition::Used),
+        (Site::It, "Usato", Condition::Used),
+        (Site::Fr, "Occasion", Condition::Used),
+        (Site::Com, "Parts Only", Condition::ForParts),
+        (Site::Com, "For parts or not working", Condition::ForParts),
+        (Site::De, "Als Ersatzteil / defekt", Condition::ForParts),
+    ];
+
+    for (site, text, condition) in cases {
+        let parsed = Condition::parse(text, site.vocabulary());
+        assert_eq!(parsed, Some(condition), "{site} {text}");
+    }
+
+    assert_eq!(Condition::parse("Sealed", Site::Com.vocabulary()), None);
+}
@@ -0,0 +1,107 @@
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+use ebay2atom::{parse_search_page, Error, ItemError, SkippedItem};
+
+const CLASSIC: &str = include_str!("fixtures/classic.html");
+
+const SEARCH_BOX: &str = r#"<input type="text" name="_nkw" value="cool gadget">"#;
+const BASE_URL: &str = r#""baseUrl":"https://www.ebay.com/sch/i.html?_nkw=cool+gadget&_sacat=0","#;
+const RESULTS: &str = r#"<ul class="srp-results srp-list srp-river-results">"#;
+
+fn run(html: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ebay2atom"))
+        .arg("--no-state")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(html.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn skips_broken_items() {
+    let html = CLASSIC
+        .replacen(r#"<span class="s-item__price">$33.00</span>"#, "", 1)
+        .replacen(
+            "https://www.ebay.com/itm/234567890123",
+            "https://www.ebay.com/help",
+            1,
+        );
+    let page = parse_search_page(&html).unwrap();
+
+    assert_eq!(page.items.len(), 4);
+    assert_eq!(
+        page.skipped,
+        [
+            SkippedItem {
+                position: 2,
+                error: ItemError::MissingPrice,
+            },
+            SkippedItem {
+                position: 3,
+                error: ItemError::InvalidLink("https://www.ebay.com/help?hash=item36a".to_owned()),
+            },
+        ]
+    );
+
+    let output = run(&html);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success());
+    assert!(stderr.contains("skipped item #2: price not found"));
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("Gadget Bundle"));
+}
+
+#[test]
+fn rejects_pages_without_search_data() {
+    let cases = [
+        (SEARCH_BOX, Error::MissingSearchTitle, 2),
+        (BASE_URL, Error::MissingSearchUrl, 3),
+        (RESULTS, Error::MissingResults, 4),
+    ];
+
+    for (needle, error, code) in cases {
+        assert!(CLASSIC.contains(needle), "{needle}");
+        let html = CLASSIC.replacen(needle, "", 1);
+
+        assert_eq!(parse_search_page(&html), Err(error.clone()));
+
+        let output = run(&html);
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert_eq!(output.status.code(), Some(code), "{error}");
+        assert!(output.stdout.is_empty());
+        assert!(stderr.contains(&error.to_string()), "{stderr}");
+    }
+}
+
+#[test]
+fn rejects_invalid_arguments() {
+    let output = Command::new(env!("CARGO_BIN_EXE_ebay2atom"))
+        .arg("--no-such-option")
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(10));
+    assert!(stderr.contains("unexpected argument"), "{stderr}");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ebay2atom"))
+        .arg("--help")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout).unwrap().contains("Usage:"));
+}
@@ -0,0 +1,103 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use chrono::Utc;
+use ebay2atom::{
+    parse_search_page,
+    render::{Feed, Record},
+};
+use serde_json::{Map, Value};
+
+const CLASSIC: &str = include_str!("fixtures/classic.html");
+
+fn export(format: &str, args: &[&str]) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ebay2atom"))
+        .args(["--no-state", "--format", format])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(CLASSIC.as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn records() -> Vec<Map<String, Value>> {
+    export("ndjson", &[])
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn exports_json_lines() {
+    let records = records();
+    let ids: Vec<_> = records.iter().map(|record| &record["id"]).collect();
+    assert_eq!(ids, ["123456789012", "234567890123", "345678901234"]);
+
+    let kit = &records[0];
+    assert_eq!(kit["title"], "15 in 1 Survival Kit");
+    assert_eq!(kit["price"], "$33.00");
+    assert_eq!(kit["price_min"], 33.0);
+    assert_eq!(kit["currency"], "USD");
+    assert_eq!(kit["total"], 38.99);
+    assert_eq!(kit["canonical_condition"], "new");
+    assert_eq!(kit["buying_format"], "buy-it-now");
+    assert_eq!(kit["seller"], "gadgetshop");
+    assert_eq!(kit["bids"], Value::Null);
+    assert_eq!(kit["new_listing"], true);
+
+    assert_eq!(records[1]["bids"], 3);
+    assert!(records[1]["ends"].is_string());
+    assert_eq!(records[2]["total"], Value::Null);
+
+    assert!(export("ndjson", &["--max-price", "0.01"]).is_empty());
+}
+
+#[test]
+fn exports_csv_rows() {
+    let csv = export("csv", &[]);
+    let mut reader = csv::Reader::from_reader(csv.as_bytes());
+
+    assert_eq!(reader.headers().unwrap(), Record::FIELDS.as_slice());
+
+    let rows: Vec<_> = reader.records().map(Result::unwrap).collect();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(&rows[0][0], "123456789012");
+    assert_eq!(&rows[0][1], "15 in 1 Survival Kit");
+    assert_eq!(&rows[1][1], "USB Flexible Mini Fan & Light <2-pack>");
+}
+
+#[test]
+fn exports_csv_header_without_listings() {
+    let csv = export("csv", &["--max-price", "0.01"]);
+    let mut lines = csv.lines();
+
+    assert!(lines
+        .next()
+        .unwrap()
+        .starts_with("id,title,new_listing,url,"));
+    assert_eq!(lines.next(), None);
+}
+
+#[test]
+fn header_matches_the_record_fields() {
+    let page = parse_search_page(CLASSIC).unwrap();
+    let feed = Feed::new(&page, Utc::now(), None);
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.serialize(Record::new(&feed.entries[0])).unwrap();
+
+    let csv = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+    assert_eq!(csv.lines().next(), Some(Record::FIELDS.join(",").as_str()));
+}
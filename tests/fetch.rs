@@ -0,0 +1,228 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    process::{Command, Output},
+    thread::{self, JoinHandle},
+};
+
+use flate2::{write::GzEncoder, Compression};
+
+const FIXTURE: &str = include_str!("fixtures/classic.html");
+
+/// A canned HTTP response.
+struct Response {
+    status: &'static str,
+    headers: Vec<String>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn ok(body: &str) -> Self {
+        Self {
+            status: "200 OK",
+            headers: Vec::new(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    fn gzip(body: &str) -> Self {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body.as_bytes()).unwrap();
+
+        Self {
+            status: "200 OK",
+            headers: vec!["Content-Encoding: gzip".to_owned()],
+            body: encoder.finish().unwrap(),
+        }
+    }
+
+    fn error(status: &'static str) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    fn header(mut self, header: &str) -> Self {
+        self.headers.push(header.to_owned());
+        self
+    }
+}
+
+/// Serve `responses` in order, one per connection, and return the base URL
+/// along with a handle yielding the received request heads.
+fn serve(responses: Vec<Response>) -> (String, JoinHandle<Vec<String>>) {
+    serve_with(|_| responses)
+}
+
+/// Like [`serve`], with responses built from the base URL.
+fn serve_with(responses: impl FnOnce(&str) -> Vec<Response>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let responses = responses(&url);
+
+    let handle = thread::spawn(move || {
+        let mut requests = Vec::new();
+
+        for response in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+
+                if line.trim().is_empty() {
+                    break;
+                }
+
+                request.push_str(&line);
+            }
+
+            let mut stream = reader.into_inner();
+            write!(stream, "HTTP/1.1 {}\r\n", response.status).unwrap();
+
+            for header in &response.headers {
+                write!(stream, "{header}\r\n").unwrap();
+            }
+
+            write!(
+                stream,
+                "Content-Length: {}\r\nConnection: close\r\n\r\n",
+                response.body.len()
+            )
+            .unwrap();
+
+            stream.write_all(&response.body).unwrap();
+            requests.push(request.to_lowercase());
+        }
+
+        requests
+    });
+
+    (url, handle)
+}
+
+fn fetch(url: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ebay2atom"))
+        .args(["--no-state", "fetch", "--backoff", "0"])
+        .args(args)
+        .arg(url)
+        .output()
+        .unwrap()
+}
+
+fn entries(output: &Output) -> usize {
+    String::from_utf8_lossy(&output.stdout)
+        .matches("<entry>")
+        .count()
+}
+
+#[test]
+fn fetches_plain_page() {
+    let (url, server) = serve(vec![Response::ok(FIXTURE)]);
+    let output = fetch(&format!("{url}/sch/i.html?_nkw=cool+gadget"), &[]);
+
+    assert!(output.status.success());
+    assert_eq!(entries(&output), 3);
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("get /sch/i.html?_nkw=cool+gadget "));
+    assert!(requests[0].contains("user-agent: ebay2atom/"));
+}
+
+#[test]
+fn decodes_gzip_page() {
+    let (url, server) = serve(vec![Response::gzip(FIXTURE)]);
+    let output = fetch(&url, &["--user-agent", "stand-in test"]);
+
+    assert!(output.status.success());
+    assert_eq!(entries(&output), 3);
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].contains("accept-encoding: gzip"));
+    assert!(requests[0].contains("user-agent: stand-in test"));
+}
+
+#[test]
+fn retries_with_cookies() {
+    let (url, server) = serve(vec![
+        Response::error("503 Service Unavailable").header("Set-Cookie: session=abc; Path=/"),
+        Response::ok(FIXTURE),
+    ]);
+
+    let output = fetch(&url, &[]);
+
+    assert!(output.status.success());
+    assert_eq!(entries(&output), 3);
+
+    let requests = server.join().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].contains("cookie: session=abc"));
+}
+
+#[test]
+fn gives_up_after_retries() {
+    let (url, server) = serve(vec![
+        Response::error("500 Internal Server Error"),
+        Response::error("500 Internal Server Error"),
+    ]);
+
+    let output = fetch(&url, &["--retries", "1"]);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_eq!(server.join().unwrap().len(), 2);
+}
+
+#[test]
+fn does_not_retry_client_errors() {
+    let (url, server) = serve(vec![Response::error("404 Not Found")]);
+    let output = fetch(&url, &[]);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_eq!(server.join().unwrap().len(), 1);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("status 404"));
+}
+
+#[test]
+fn follows_pagination() {
+    // The second page repeats the listings of the first one
+    let (url, server) = serve_with(|url| {
+        let link = format!(r#"</ul><a href="{url}/sch/i.html?_pgn=2">2</a>"#);
+        vec![
+            Response::ok(&FIXTURE.replace("</ul>", &link)),
+            Response::ok(FIXTURE),
+        ]
+    });
+    let output = fetch(&url, &["--pages", "3"]);
+
+    assert!(output.status.success());
+    assert_eq!(entries(&output), 3);
+
+    let requests = server.join().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].starts_with("get /sch/i.html?_pgn=2 "));
+}
+
+#[test]
+fn rejects_invalid_durations() {
+    for option in ["--timeout", "--backoff"] {
+        for value in ["-1", "nan", "inf", "1e30", "soon"] {
+            let output = Command::new(env!("CARGO_BIN_EXE_ebay2atom"))
+                .args(["--no-state", "fetch", &format!("{option}={value}")])
+                .arg("http://127.0.0.1:9/sch/i.html")
+                .output()
+                .unwrap();
+
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            assert_eq!(output.status.code(), Some(10), "{option}={value}");
+            assert!(
+                stderr.contains("invalid value"),
+                "{option}={value}: {stderr}"
+            );
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::{env, fs, process};
+
+use ebay2atom::{
+    filter::{Limit, Rejection},
+    parse_search_page, BuyingFormat, Condition, Filter,
+};
+
+const CLASSIC: &str = include_str!("fixtures/classic.html");
+
+/// The item numbers of the classic fixture listings kept by `filter`.
+fn kept(filter: &Filter) -> Vec<String> {
+    let mut page = parse_search_page(CLASSIC).unwrap();
+    page.retain(|item| filter.matches(item));
+    page.items.into_iter().map(|item| item.id).collect()
+}
+
+#[test]
+fn bounds_prices_and_totals() {
+    let filter = Filter {
+        max_price: Some("9".parse().unwrap()),
+        ..Filter::default()
+    };
+    assert_eq!(
+        kept(&filter),
+        ["234567890123", "678901234567", "456789012345"]
+    );
+
+    let filter = Filter {
+        min_total: Some("5".parse().unwrap()),
+        max_total: Some("30.00 USD".parse().unwrap()),
+        ..Filter::default()
+    };
+    assert_eq!(
+        kept(&filter),
+        ["234567890123", "678901234567", "567890123456"]
+    );
+
+    let filter = Filter {
+        max_price: Some("100 EUR".parse().unwrap()),
+        ..Filter::default()
+    };
+    assert!(kept(&filter).is_empty());
+}
+
+#[test]
+fn allows_conditions_and_formats() {
+    let filter = Filter {
+        conditions: vec![Condition::Used, Condition::OpenBox],
+        ..Filter::default()
+    };
+    assert_eq!(kept(&filter), ["234567890123", "345678901234"]);
+
+    let filter = Filter {
+        formats: vec![BuyingFormat::BuyItNow, BuyingFormat::BestOffer],
+        ..Filter::default()
+    };
+    assert_eq!(kept(&filter), ["123456789012", "345678901234"]);
+}
+
+#[test]
+fn matches_titles_and_sellers() {
+    let filter = Filter {
+        keywords: vec!["GADGET".to_owned()],
+        exclude_keywords: vec!["case".to_owned()],
+        ..Filter::default()
+    };
+    assert_eq!(
+        kept(&filter),
+        ["345678901234", "678901234567", "567890123456"]
+    );
+
+    let filter = Filter {
+        keywords: vec!["gadge".to_owned()],
+        ..Filter::default()
+    };
+    assert!(kept(&filter).is_empty());
+
+    let filter = Filter {
+        title_regexes: vec!["(?i)^(usb|gadget) ".parse().unwrap()],
+        exclude_title_regexes: vec!["Stand|Case".parse().unwrap()],
+        ..Filter::default()
+    };
+    assert_eq!(kept(&filter), ["234567890123", "345678901234"]);
+
+    let filter = Filter {
+        blocked_sellers: vec!["GadgetShop".to_owned()],
+        ..Filter::default()
+    };
+    assert_eq!(kept(&filter).len(), 5);
+}
+
+#[test]
+fn explains_rejections() {
+    let page = parse_search_page(CLASSIC).unwrap();
+    let filter = Filter {
+        max_total: Some("30".parse().unwrap()),
+        ..Filter::default()
+    };
+
+    let rejections: Vec<_> = page
+        .items
+        .iter()
+        .filter_map(|item| filter.check(item).err())
+        .map(|rejection| rejection.to_string())
+        .collect();
+    assert_eq!(rejections, ["total above 30.00", "unknown total"]);
+
+    let filter = Filter {
+        min_price: Some("1 GBP".parse().unwrap()),
+        ..Filter::default()
+    };
+    assert_eq!(
+        filter.check(&page.items[0]),
+        Err(Rejection::Currency {
+            field: "price",
+            currency: "USD".to_owned(),
+        })
+    );
+}
+
+#[test]
+fn parses_limits() {
+    let limit: Limit = "12.5 EUR".parse().unwrap();
+    assert_eq!(limit.cents, 1250);
+    assert_eq!(limit.currency.as_deref(), Some("EUR"));
+    assert_eq!(limit.to_string(), "12.50 EUR");
+
+    let limit: Limit = "500 €".parse().unwrap();
+    assert_eq!(limit.cents, 50_000);
+    assert_eq!(limit.currency.as_deref(), Some("EUR"));
+
+    for text in [
+        "",
+        "cheap",
+        "1,000",
+        "12.345",
+        "12.345 EUR",
+        "10 pesos",
+        "£500",
+        "500 EURO",
+        "5 CHFfoo",
+        "5 US $ off",
+    ] {
+        assert!(text.parse::<Limit>().is_err(), "{text}");
+    }
+}
+
+#[test]
+fn reads_filter_files() {
+    let path = env::temp_dir().join(format!("ebay2atom-filter-{}.toml", process::id()));
+    fs::write(
+        &path,
+        r#"
+            max_price = 20
+            min_total = "5 USD"
+            conditions = ["new"]
+            exclude_keywords = ["case"]
+            formats = ["buy-it-now"]
+        "#,
+    )
+    .unwrap();
+    let filter = Filter::read(&path);
+    fs::write(&path, "conditions = [\"mint\"]").unwrap();
+    let invalid = Filter::read(&path);
+    fs::remove_file(&path).unwrap();
+
+    let filter = filter.unwrap();
+    assert_eq!(filter.max_price.unwrap().cents, 2000);
+    assert_eq!(filter.conditions, [Condition::New]);
+    assert_eq!(filter.formats, [BuyingFormat::BuyItNow]);
+    assert!(invalid
+        .unwrap_err()
+        .to_string()
+        .contains("unknown condition"));
+}
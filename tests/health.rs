@@ -0,0 +1,99 @@
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+use ebay2atom::{health::check_page, Layout, Profile};
+
+const CLASSIC: &str = include_str!("fixtures/classic.html");
+
+/// The classic fixture with listings the item selector no longer matches.
+fn drifted() -> String {
+    CLASSIC.replace("s-item__wrapper", "s-item__box")
+}
+
+fn run(html: &str, args: &[&str]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ebay2atom"))
+        .arg("--no-state")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(html.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn reports_item_fields() {
+    let report = check_page(CLASSIC, &Profile::default());
+
+    assert_eq!(report.layout, Layout::Classic);
+    assert!(report.search_title);
+    assert!(report.results);
+    assert_eq!(report.items, 7);
+    assert_eq!(report.extracted, 6);
+    assert_eq!(report.skipped, 0);
+    assert!(!report.drift);
+    assert_eq!(report.error, None);
+
+    let fields: Vec<_> = report.fields.iter().map(|(field, _)| *field).collect();
+    assert_eq!(
+        fields,
+        [
+            "title",
+            "link",
+            "price",
+            "shipping",
+            "condition",
+            "time_left",
+            "purchase_options",
+            "ad",
+            "bids",
+            "seller",
+            "image",
+            "sponsored",
+        ]
+    );
+    assert!(report.fields.contains(&("title", 7)));
+    assert!(report.fields.contains(&("time_left", 1)));
+
+    let text = report.to_string();
+    assert!(text.contains("Items: 7\n  title: 7/7\n"), "{text}");
+    assert!(text.contains("Extracted: 6\n"), "{text}");
+}
+
+#[test]
+fn reports_drift() {
+    let report = check_page(&drifted(), &Profile::default());
+
+    assert!(report.results);
+    assert_eq!(report.items, 0);
+    assert_eq!(report.extracted, 0);
+    assert!(report.item_links >= 3);
+    assert!(report.drift);
+}
+
+#[test]
+fn drift_fails_only_the_check() {
+    let output = run(&drifted(), &["--check"]);
+    assert_eq!(output.status.code(), Some(8));
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("Items: 0"));
+
+    let output = run(&drifted(), &[]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success());
+    assert!(stderr.contains("warning:"), "{stderr}");
+
+    let output = run(CLASSIC, &["--check"]);
+    assert!(output.status.success());
+}
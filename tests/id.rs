@@ -0,0 +1,63 @@
+use ebay2atom::{
+    id::{entry_id, feed_id, normalize_search_url},
+    parse_search_page,
+};
+
+const CLASSIC: &str = include_str!("fixtures/classic.html");
+
+#[test]
+fn normalizes_search_urls() {
+    let url = "https://ebay.com/sch/i.html?_nkw=cool+gadget&_sacat=0";
+    let equivalents = [
+        "https://www.ebay.com/sch/i.html?_nkw=cool+gadget&_sacat=0",
+        "http://WWW.eBay.com/sch/i.html?_sacat=0&_nkw=cool+gadget",
+        "https://www.ebay.com/sch/i.html?_nkw=cool%20gadget&_sacat=0&_pgn=2",
+        "https://www.ebay.com/sch/i.html?_from=R40&_trksid=p2380057&_nkw=cool+gadget&_sacat=0&rt=nc",
+        "https://www.ebay.com/sch/i.html?_nkw=cool+gadget&_udlo=&_sacat=0#item1",
+    ];
+
+    for equivalent in equivalents {
+        assert_eq!(normalize_search_url(equivalent), url, "{equivalent}");
+    }
+
+    assert_eq!(
+        normalize_search_url("https://www.ebay.de"),
+        "https://ebay.de/"
+    );
+    assert_ne!(
+        normalize_search_url("https://www.ebay.com/sch/i.html?_nkw=cool+gadget&_sacat=58058"),
+        url
+    );
+}
+
+#[test]
+fn identifies_entries_by_item_number() {
+    assert_eq!(
+        entry_id("123456789012"),
+        "tag:ebay.com,2024:item:123456789012"
+    );
+}
+
+#[test]
+fn identifies_feeds_by_the_whole_search() {
+    let page = parse_search_page(CLASSIC).unwrap();
+    assert_eq!(
+        page.url,
+        "https://www.ebay.com/sch/i.html?_nkw=cool+gadget&_sacat=0"
+    );
+
+    let filtered = CLASSIC.replace("_sacat=0\"", r#"_sacat=0&_udhi=50&LH_ItemCondition=1000""#);
+    let filtered = parse_search_page(&filtered).unwrap();
+    assert_eq!(
+        filtered.url,
+        "https://www.ebay.com/sch/i.html?_nkw=cool+gadget&_sacat=0&_udhi=50&LH_ItemCondition=1000"
+    );
+    assert_eq!(
+        feed_id(&filtered.url),
+        "https://ebay.com/sch/i.html?LH_ItemCondition=1000&_nkw=cool+gadget&_sacat=0&_udhi=50"
+    );
+    assert_ne!(feed_id(&page.url), feed_id(&filtered.url));
+
+    let escaped = CLASSIC.replace("gadget&_sacat=0", r"gadget\u0026_sacat=0");
+    assert_eq!(parse_search_page(&escaped).unwrap().url, page.url);
+}
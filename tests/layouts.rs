@@ -0,0 +1,143 @@
+use ebay2atom::{image, parse_search_page, BuyingFormat, Layout, Section, Site, Title};
+
+const CLASSIC: &str = include_str!("fixtures/classic.html");
+const CARD: &str = include_str!("fixtures/card.html");
+
+#[test]
+fn detects_layouts() {
+    assert_eq!(parse_search_page(CLASSIC).unwrap().layout, Layout::Classic);
+    assert_eq!(parse_search_page(CARD).unwrap().layout, Layout::Card);
+}
+
+#[test]
+fn layouts_yield_the_same_items() {
+    let classic = parse_search_page(CLASSIC).unwrap();
+    let card = parse_search_page(CARD).unwrap();
+
+    assert_eq!(classic.items.len(), 6);
+    assert!(classic.skipped.is_empty());
+    assert_eq!(classic.items, card.items);
+    assert_eq!(classic.skipped, card.skipped);
+}
+
+#[test]
+fn tags_sections() {
+    for html in [CLASSIC, CARD] {
+        let mut page = parse_search_page(html).unwrap();
+        let sections: Vec<_> = page.items.iter().map(|item| item.section).collect();
+
+        assert_eq!(
+            sections,
+            [
+                Section::Exact,
+                Section::Exact,
+                Section::Exact,
+                Section::Exact,
+                Section::FewerWords,
+                Section::International,
+            ]
+        );
+
+        page.retain(|item| item.section == Section::Exact);
+        assert_eq!(page.items.len(), 4);
+        assert_eq!(page.excluded, 2);
+        assert!(!page.is_drifted());
+    }
+}
+
+#[test]
+fn flags_sponsored_listings() {
+    for html in [CLASSIC, CARD] {
+        let page = parse_search_page(html).unwrap();
+        let sponsored: Vec<_> = page
+            .items
+            .iter()
+            .filter(|item| item.sponsored)
+            .map(|item| item.id.as_str())
+            .collect();
+
+        assert_eq!(sponsored, ["678901234567"]);
+    }
+}
+
+#[test]
+fn cleans_titles() {
+    for html in [CLASSIC, CARD] {
+        let page = parse_search_page(html).unwrap();
+        let titles: Vec<_> = page
+            .items
+            .iter()
+            .map(|item| (item.title.as_str(), item.new_listing))
+            .collect();
+
+        assert_eq!(
+            titles[..4],
+            [
+                ("15 in 1 Survival Kit", true),
+                ("USB Flexible Mini Fan & Light <2-pack>", false),
+                ("Gadget Bundle", false),
+                ("Gadget Stand", true),
+            ]
+        );
+    }
+}
+
+#[test]
+fn joins_title_nodes() {
+    let vocabulary = Site::Com.vocabulary();
+
+    let title = Title::parse(["Gadget", "Stand"], vocabulary);
+    assert_eq!(title.text, "Gadget Stand");
+
+    let title = Title::parse(["New Listing", "USB ", " Mini", "Fan\n"], vocabulary);
+    assert_eq!(title.text, "USB Mini Fan");
+    assert!(title.new_listing);
+
+    let html = CARD.replace(
+        r#"<span class="su-styled-text primary default">15 in 1 Survival Kit</span>"#,
+        r#"<span class="su-styled-text primary default">15 in 1</span><span class="su-styled-text primary default">Survival Kit</span>"#,
+    );
+    assert_ne!(html, CARD);
+    let page = parse_search_page(&html).unwrap();
+    assert_eq!(page.items[0].title, "15 in 1 Survival Kit");
+}
+
+#[test]
+fn classifies_buying_formats() {
+    for html in [CLASSIC, CARD] {
+        let page = parse_search_page(html).unwrap();
+        let buying: Vec<_> = page.items.iter().map(|item| item.buying).collect();
+
+        assert_eq!(buying[0].format, Some(BuyingFormat::BuyItNow));
+        assert_eq!(buying[1].format, Some(BuyingFormat::Auction));
+        assert_eq!(buying[1].bids, Some(3));
+        assert_eq!(buying[2].format, Some(BuyingFormat::BestOffer));
+        assert!(buying[2].accepts_offers);
+    }
+}
+
+#[test]
+fn extracts_images() {
+    for html in [CLASSIC, CARD] {
+        let page = parse_search_page(html).unwrap();
+        let images: Vec<_> = page
+            .items
+            .iter()
+            .map(|item| item.image.as_deref())
+            .collect();
+
+        assert_eq!(
+            images[..3],
+            [
+                Some("https://i.ebayimg.com/images/g/abc/s-l500.webp"),
+                Some("https://i.ebayimg.com/images/g/def/s-l300.jpg"),
+                None,
+            ]
+        );
+    }
+
+    assert_eq!(
+        image::mime_type("https://i.ebayimg.com/images/g/abc/s-l500.webp?set_id=1"),
+        Some("image/webp")
+    );
+}
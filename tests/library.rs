@@ -0,0 +1,53 @@
+use ebay2atom::{parse_search_page, Money, Price};
+
+const PAGE: &str = r#"<!DOCTYPE html>
+<html><head><script>{"baseUrl":"https://www.ebay.com/sch/i.html?_nkw=cool+gadget&_sacat=0"}</script></head>
+<body>
+<input name="_nkw" value="cool gadget">
+<div class="srp-river"><ul class="srp-river-results">
+<li><div class="s-item__wrapper">
+  <a class="s-item__link" href="https://www.ebay.com/itm/123456789012"><div class="s-item__title"><span role="heading">15 in 1 Survival Kit</span></div></a>
+  <span class="SECONDARY_INFO">Brand New</span>
+  <span class="s-item__price">$33.00</span>
+  <span class="s-item__purchase-options">Buy It Now</span>
+</div></li>
+<li><div class="s-item__wrapper">
+  <a class="s-item__link" href="https://www.ebay.com/itm/234567890123"><div class="s-item__title"><span role="heading">USB Flexible Mini Fan</span></div></a>
+  <span class="s-item__price">$6.92</span>
+  <span class="s-item__time-left">2d 4h left</span>
+</div></li>
+</ul></div>
+</body></html>
+"#;
+
+#[test]
+fn parses_search_pages() {
+    let page = parse_search_page(PAGE).unwrap();
+
+    assert_eq!(page.title, "cool gadget");
+    assert_eq!(
+        page.url,
+        "https://www.ebay.com/sch/i.html?_nkw=cool+gadget&_sacat=0"
+    );
+    assert_eq!(page.items.len(), 2);
+}
+
+#[test]
+fn parses_items() {
+    let page = parse_search_page(PAGE).unwrap();
+
+    let kit = &page.items[0];
+    assert_eq!(kit.title, "15 in 1 Survival Kit");
+    assert_eq!(kit.id, "123456789012");
+    assert_eq!(kit.url, "https://www.ebay.com/itm/123456789012");
+    assert_eq!(kit.price, Price::parse("$33.00"));
+    assert_eq!(kit.condition.as_deref(), Some("Brand New"));
+    assert_eq!(kit.time_left, None);
+    assert_eq!(kit.purchase_options.as_deref(), Some("Buy It Now"));
+
+    let fan = &page.items[1];
+    assert_eq!(fan.id, "234567890123");
+    assert_eq!(fan.price.min, Some(Money::new(692, "USD")));
+    assert_eq!(fan.condition, None);
+    assert_eq!(fan.time_left.as_deref(), Some("2d 4h left"));
+}
@@ -0,0 +1,57 @@
+use ebay2atom::{Money, Price};
+
+#[test]
+fn parses_prices() {
+    let cases = [
+        ("$33.00", 3300, "USD"),
+        ("EUR 1.234,56", 123_456, "EUR"),
+        ("£12.50", 1250, "GBP"),
+        ("US $1,299", 129_900, "USD"),
+    ];
+
+    for (raw, cents, currency) in cases {
+        let price = Price::parse(raw);
+        assert_eq!(price.min, Some(Money::new(cents, currency)), "{raw}");
+        assert_eq!(price.max, None, "{raw}");
+        assert!(!price.is_range());
+        assert_eq!(price.to_string(), raw);
+    }
+}
+
+#[test]
+fn parses_ranges() {
+    let price = Price::parse("$10.00 to $25.99");
+    assert!(price.is_range());
+    assert_eq!(price.min, Some(Money::new(1000, "USD")));
+    assert_eq!(price.max, Some(Money::new(2599, "USD")));
+}
+
+#[test]
+fn rejects_overflowing_amounts() {
+    let price = Price::parse("$99999999999999999.00");
+    assert_eq!(price.min, None);
+    assert_eq!(price.raw, "$99999999999999999.00");
+
+    let price = Price::parse("$10.00 to $99999999999999999.00");
+    assert_eq!(price.min, None);
+    assert_eq!(price.max, None);
+
+    let large = Money::new(i64::MAX, "USD");
+    assert_eq!(large.checked_add(&Money::new(1, "USD")), None);
+}
+
+#[test]
+fn handles_money() {
+    let price = Money::new(1250, "EUR");
+    assert_eq!(price.to_string(), "12.50 EUR");
+    assert_eq!(Money::new(-5, "EUR").to_string(), "-0.05 EUR");
+    assert_eq!(price.amount(), 12.5);
+
+    assert_eq!(
+        price.checked_add(&Money::new(599, "EUR")),
+        Some(Money::new(1849, "EUR"))
+    );
+    assert_eq!(price.checked_add(&Money::new(599, "USD")), None);
+    assert!(price > Money::new(1000, "EUR"));
+    assert_eq!(price.partial_cmp(&Money::new(1000, "USD")), None);
+}
@@ -0,0 +1,113 @@
+use std::{
+    env, fs,
+    io::Write,
+    path::PathBuf,
+    process::{self, Command, Stdio},
+};
+
+use ebay2atom::{
+    parse_search_page, parse_search_page_with, profile::ProfileError, Error, Profile,
+    CARD_PRICE_QUERY, ITEM_ID_REGEX,
+};
+
+const CLASSIC: &str = include_str!("fixtures/classic.html");
+
+/// Write `toml` to a profile file named after `test`.
+fn profile_file(test: &str, toml: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("ebay2atom-profile-{test}-{}.toml", process::id()));
+    fs::write(&path, toml).unwrap();
+    path
+}
+
+fn read(test: &str, toml: &str) -> Result<Profile, ProfileError> {
+    let path = profile_file(test, toml);
+    let profile = Profile::read(&path);
+    fs::remove_file(path).unwrap();
+    profile
+}
+
+#[test]
+fn merges_profiles_over_the_defaults() {
+    let profile = read(
+        "merge",
+        r#"
+            item_id_regex = "/itm/(\\d{12})"
+
+            [classic]
+            price = ".s-item__price, .s-item__detail--price"
+        "#,
+    )
+    .unwrap();
+
+    let default = Profile::default();
+    assert_eq!(profile.item_id_regex, r"/itm/(\d{12})");
+    assert_eq!(
+        profile.classic.price,
+        ".s-item__price, .s-item__detail--price"
+    );
+    assert_eq!(profile.classic.title, default.classic.title);
+    assert_eq!(profile.card.price, CARD_PRICE_QUERY);
+    assert_eq!(profile.search_title, default.search_title);
+
+    let page = parse_search_page_with(CLASSIC, &profile).unwrap();
+    assert_eq!(page.items, parse_search_page(CLASSIC).unwrap().items);
+}
+
+#[test]
+fn round_trips_the_default_profile() {
+    let profile = read("default", &Profile::default().to_toml()).unwrap();
+    assert_eq!(profile, Profile::default());
+    assert_eq!(profile.item_id_regex, ITEM_ID_REGEX);
+}
+
+#[test]
+fn rejects_invalid_profiles() {
+    let cases = [
+        ("item_id_regex = '/itm/\\d+'", "invalid item_id_regex"),
+        ("base_url_regex = 'baseUrl'", "invalid base_url_regex"),
+        (
+            "page_number_regex = '_pgn=\\d+'",
+            "invalid page_number_regex",
+        ),
+        ("item_url_regex = '/itm/('", "invalid item_url_regex"),
+        ("[card]\nprice = '..'", "invalid card.price"),
+        ("[classic]\nprize = '.price'", "unknown field"),
+        ("search_title = ", "invalid profile"),
+    ];
+
+    for (index, (toml, message)) in cases.into_iter().enumerate() {
+        let error = read(&format!("invalid-{index}"), toml).unwrap_err();
+        assert!(error.to_string().contains(message), "{toml}: {error}");
+    }
+
+    let profile = Profile {
+        item_id_regex: r"/itm/\d+".to_owned(),
+        ..Profile::default()
+    };
+    assert!(matches!(
+        parse_search_page_with(CLASSIC, &profile),
+        Err(Error::InvalidProfile(_))
+    ));
+}
+
+#[test]
+fn exits_on_invalid_profiles() {
+    let path = profile_file("exit", "item_id_regex = '/itm/\\d+'");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ebay2atom"))
+        .args(["--no-state", "--profile"])
+        .arg(&path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // The profile is loaded before the page is read
+    let _ = child.stdin.take().unwrap().write_all(CLASSIC.as_bytes());
+    let output = child.wait_with_output().unwrap();
+    fs::remove_file(path).unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(7));
+    assert!(stderr.contains("has no capture group"), "{stderr}");
+}
@@ -0,0 +1,191 @@
+use std::{env, fs, process};
+
+use ebay2atom::{filter::Rejection, parse_search_page, Filter, Rule};
+
+const CLASSIC: &str = include_str!("fixtures/classic.html");
+
+/// The item numbers of the classic fixture listings matching `rule`.
+fn kept(rule: &str) -> Vec<String> {
+    let rule: Rule = rule.parse().unwrap();
+    let mut page = parse_search_page(CLASSIC).unwrap();
+    page.retain(|item| rule.matches(item));
+    page.items.into_iter().map(|item| item.id).collect()
+}
+
+#[test]
+fn compares_amounts() {
+    assert_eq!(
+        kept("price < 9"),
+        ["234567890123", "678901234567", "456789012345"]
+    );
+    assert_eq!(kept("total >= $27"), ["123456789012", "567890123456"]);
+    assert_eq!(
+        kept("shipping == 0 USD"),
+        ["234567890123", "678901234567", "456789012345"]
+    );
+    assert!(kept("price < 100 EUR").is_empty());
+    assert_eq!(kept("price <= 4.50").len(), 1);
+}
+
+#[test]
+fn compares_counts_and_durations() {
+    assert_eq!(kept("bids > 2"), ["234567890123"]);
+    assert_eq!(kept("time_left < 2d 4h 1m"), ["234567890123"]);
+    assert!(kept("time_left < 2d4h").is_empty());
+    assert!(kept("bids == 0").is_empty());
+    assert_eq!(kept("time_left < 99999999999d"), ["234567890123"]);
+    assert!(kept("time_left >= 400d 1h").is_empty());
+}
+
+#[test]
+fn matches_texts() {
+    assert_eq!(
+        kept("title ~ 'gadget' and title !~ /stand|case/i"),
+        ["345678901234", "567890123456"]
+    );
+    assert_eq!(kept(r#"title == "gadget bundle""#), ["345678901234"]);
+    assert_eq!(kept("seller ~ /^gadget/"), ["123456789012"]);
+    assert_eq!(kept("seller != gadgetshop").len(), 0);
+}
+
+#[test]
+fn matches_names() {
+    assert_eq!(
+        kept("condition == open_box or condition == used"),
+        ["234567890123", "345678901234"]
+    );
+    assert_eq!(kept("format == 'buy it now'"), ["123456789012"]);
+    assert_eq!(
+        kept("not section == exact"),
+        ["456789012345", "567890123456"]
+    );
+}
+
+#[test]
+fn combines_comparisons() {
+    assert_eq!(
+        kept("condition == new and (price < 5 or shipping > 10)"),
+        ["456789012345", "567890123456"]
+    );
+    assert_eq!(kept("NOT (condition == new OR bids > 0)"), ["345678901234"]);
+    assert_eq!(
+        kept("condition == new and price < 5 or shipping > 10"),
+        ["456789012345", "567890123456"]
+    );
+}
+
+#[test]
+fn explains_outcomes() {
+    let page = parse_search_page(CLASSIC).unwrap();
+    let rule: Rule = "total < 30 and condition == new".parse().unwrap();
+
+    let outcomes: Vec<_> = page
+        .items
+        .iter()
+        .map(|item| rule.evaluate(item).to_string())
+        .collect();
+    assert_eq!(
+        outcomes,
+        [
+            "total < 30 is false (total is 38.99 USD)",
+            "condition == new is false (condition is used)",
+            "total < 30 is false (total is unknown)",
+            "total < 30 is true (total is 8.99 USD), condition == new is true (condition is new)",
+            "total < 30 is true (total is 4.50 USD), condition == new is true (condition is new)",
+            "total < 30 is true (total is 27.00 USD), condition == new is true (condition is new)",
+        ]
+    );
+
+    let rule: Rule = "time_left > 1d".parse().unwrap();
+    assert_eq!(
+        rule.evaluate(&page.items[1]).to_string(),
+        "time_left > 1d is true (time_left is 2d 4h)"
+    );
+}
+
+#[test]
+fn reports_errors() {
+    let cases = [
+        (
+            "titel ~ gadget",
+            1,
+            "unknown field \"titel\", did you mean \"title\"?",
+        ),
+        ("price = 5", 7, "use == to compare values"),
+        (
+            "price < used",
+            9,
+            "expected an amount such as 500 or 12.50 EUR, found \"used\"",
+        ),
+        (
+            "condition < new",
+            11,
+            "< cannot compare condition, use == or !=",
+        ),
+        ("condition == mint", 14, "unknown condition \"mint\""),
+        (
+            "(price < 5 or bids > 1",
+            1,
+            "unclosed \"(\", add the closing \")\"",
+        ),
+        (
+            "title ~ 'gadget",
+            9,
+            "unterminated string, add the closing quote",
+        ),
+        (
+            "title ~ /gadget/q",
+            17,
+            "unknown regex flag 'q', expected i, m, s or x",
+        ),
+        (
+            "price < 5 EUR USD",
+            15,
+            r#"expected "and", "or" or the end of the rule, found "USD""#,
+        ),
+    ];
+
+    for (source, column, message) in cases {
+        let error = source.parse::<Rule>().unwrap_err();
+        assert_eq!(error.column(), column, "{source}");
+        assert!(
+            error.message.starts_with(message),
+            "{source}: {}",
+            error.message
+        );
+    }
+
+    let error = "bids > lots".parse::<Rule>().unwrap_err();
+    assert!(error
+        .to_string()
+        .ends_with("\n    bids > lots\n           ^"));
+}
+
+#[test]
+fn reads_rules_from_filter_files() {
+    let path = env::temp_dir().join(format!("ebay2atom-rule-{}.toml", process::id()));
+    fs::write(&path, r#"rules = ["title ~ gadget", "total < 20"]"#).unwrap();
+    let filter = Filter::read(&path);
+    fs::write(&path, r#"rules = ["total <"]"#).unwrap();
+    let invalid = Filter::read(&path);
+    fs::remove_file(&path).unwrap();
+
+    let filter = filter.unwrap();
+    let page = parse_search_page(CLASSIC).unwrap();
+    let kept: Vec<_> = page
+        .items
+        .iter()
+        .filter(|item| filter.matches(item))
+        .map(|item| item.id.as_str())
+        .collect();
+    assert_eq!(kept, ["678901234567", "456789012345"]);
+
+    match filter.check(&page.items[5]) {
+        Err(Rejection::Rule { rule, outcome }) => {
+            assert_eq!(rule, "total < 20");
+            assert!(!outcome.matched);
+        }
+        other => panic!("unexpected {other:?}"),
+    }
+    assert!(invalid.unwrap_err().to_string().contains("at column 8"));
+}
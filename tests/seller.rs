@@ -0,0 +1,22 @@
+use ebay2atom::Seller;
+
+#[test]
+fn parses_sellers() {
+    let cases = [
+        ("gadgetshop (987) 99.5%", Some(987), Some(995)),
+        ("gadgetshop (1,234) 100%", Some(1234), Some(1000)),
+        ("gadgetshop 99.5% positive (1.2K)", Some(1200), Some(995)),
+        ("gadgetshop 99,5 % positiv (12K)", Some(12000), Some(995)),
+        ("gadgetshop (1.234) 98,1%", Some(1234), Some(981)),
+        ("gadgetshop", None, None),
+    ];
+
+    for (text, feedback, positive_permille) in cases {
+        let seller = Seller::parse(text).unwrap();
+        assert_eq!(seller.name, "gadgetshop", "{text}");
+        assert_eq!(seller.feedback, feedback, "{text}");
+        assert_eq!(seller.positive_permille, positive_permille, "{text}");
+    }
+
+    assert_eq!(Seller::parse(""), None);
+}
@@ -0,0 +1,81 @@
+use ebay2atom::{Money, Shipping, ShippingCost, Site};
+
+#[test]
+fn parses_shipping() {
+    let cases = [
+        (
+            Site::Com,
+            "+$5.99 shipping",
+            ShippingCost::Paid(Money::new(599, "USD")),
+        ),
+        (
+            Site::Com,
+            "+$12.00 delivery",
+            ShippingCost::Paid(Money::new(1200, "USD")),
+        ),
+        (
+            Site::De,
+            "+ EUR 4,99 Versand",
+            ShippingCost::Paid(Money::new(499, "EUR")),
+        ),
+        (
+            Site::CoUk,
+            "+£3.50 postage",
+            ShippingCost::Paid(Money::new(350, "GBP")),
+        ),
+        (
+            Site::It,
+            "+EUR 1.250,00 di spedizione",
+            ShippingCost::Paid(Money::new(125_000, "EUR")),
+        ),
+        (
+            Site::Fr,
+            "+12,50 EUR de livraison",
+            ShippingCost::Paid(Money::new(1250, "EUR")),
+        ),
+        (Site::Com, "Free shipping", ShippingCost::Free),
+        (Site::CoUk, "Free postage", ShippingCost::Free),
+        (Site::De, "Kostenloser Versand", ShippingCost::Free),
+        (Site::It, "Spedizione gratuita", ShippingCost::Free),
+        (Site::Fr, "Livraison gratuite", ShippingCost::Free),
+        (Site::Com, "Free local pickup", ShippingCost::LocalPickup),
+        (
+            Site::CoUk,
+            "Free local collection",
+            ShippingCost::LocalPickup,
+        ),
+        (Site::De, "Nur Abholung", ShippingCost::LocalPickup),
+        (
+            Site::Com,
+            "Shipping not specified",
+            ShippingCost::NotSpecified,
+        ),
+        (
+            Site::De,
+            "Versand nicht angegeben",
+            ShippingCost::NotSpecified,
+        ),
+        (Site::Com, "Ships soon", ShippingCost::NotSpecified),
+    ];
+
+    for (site, raw, cost) in cases {
+        assert_eq!(Shipping::parse(raw, site).cost, cost, "{site} {raw}");
+    }
+}
+
+#[test]
+fn computes_totals() {
+    let price = Money::new(3300, "USD");
+
+    let paid = Shipping::parse("+$5.99 shipping", Site::Com);
+    assert_eq!(paid.total(&price), Some(Money::new(3899, "USD")));
+
+    let free = Shipping::parse("Free shipping", Site::Com);
+    assert_eq!(free.total(&price), Some(price.clone()));
+
+    let foreign = Shipping::parse("+EUR 5,00 shipping", Site::Com);
+    assert_eq!(foreign.total(&price), None);
+
+    let unknown = Shipping::parse("Shipping not specified", Site::Com);
+    assert_eq!(unknown.total(&price), None);
+}
@@ -0,0 +1,178 @@
+use chrono::Duration;
+use ebay2atom::{parse_search_page, BuyingFormat, Condition, Money, Section, ShippingCost, Site};
+
+const CLASSIC: &str = include_str!("fixtures/classic.html");
+const DE: &str = include_str!("fixtures/ebay.de.html");
+const CO_UK: &str = include_str!("fixtures/ebay.co.uk.html");
+const IT: &str = include_str!("fixtures/ebay.it.html");
+const FR: &str = include_str!("fixtures/ebay.fr.html");
+
+/// The localized pages, with their site and the first listing title.
+const PAGES: [(Site, &str, &str); 4] = [
+    (Site::De, DE, "Überlebensset 15 in 1"),
+    (Site::CoUk, CO_UK, "15 in 1 Survival Kit"),
+    (Site::It, IT, "Kit di sopravvivenza 15 in 1"),
+    (Site::Fr, FR, "Kit de survie 15 en 1"),
+];
+
+#[test]
+fn identifies_sites() {
+    let cases = [
+        (
+            "https://www.ebay.com/sch/i.html?_nkw=gadget",
+            Some(Site::Com),
+        ),
+        ("https://www.ebay.de/sch/i.html?_nkw=gadget", Some(Site::De)),
+        ("https://ebay.co.uk/sch/i.html", Some(Site::CoUk)),
+        ("https://www.EBAY.it/itm/123", Some(Site::It)),
+        ("https://www.ebay.fr", Some(Site::Fr)),
+        ("https://www.ebay.com.au/sch/i.html", None),
+        ("https://www.notebay.de/sch/i.html", None),
+    ];
+
+    for (url, site) in cases {
+        assert_eq!(Site::from_url(url), site, "{url}");
+    }
+
+    assert_eq!(parse_search_page(CLASSIC).unwrap().site, Site::Com);
+    for (site, html, _) in PAGES {
+        assert_eq!(parse_search_page(html).unwrap().site, site);
+    }
+}
+
+#[test]
+fn parses_localized_pages() {
+    for (site, html, title) in PAGES {
+        let page = parse_search_page(html).unwrap();
+        let currency = site.currency();
+
+        assert!(page.skipped.is_empty(), "{site}");
+        assert_eq!(page.items.len(), 5, "{site}");
+
+        let first = &page.items[0];
+        assert_eq!(first.title, title, "{site}");
+        assert!(first.new_listing, "{site}");
+        assert_eq!(first.id, "123456789012", "{site}");
+        assert_eq!(
+            first.price.min,
+            Some(Money::new(123_456, currency)),
+            "{site}"
+        );
+        assert_eq!(first.total(), Some(Money::new(124_055, currency)), "{site}");
+
+        let auction = &page.items[1];
+        assert_eq!(auction.buying.bids, Some(3), "{site}");
+        assert_eq!(
+            auction.ends_in,
+            Some(Duration::days(2) + Duration::hours(4)),
+            "{site}"
+        );
+        assert!(auction.sponsored, "{site}");
+
+        let range = &page.items[2];
+        assert!(!range.new_listing, "{site}");
+        assert_eq!(range.price.max, Some(Money::new(2599, currency)), "{site}");
+
+        let conditions: Vec<_> = page
+            .items
+            .iter()
+            .map(|item| item.canonical_condition)
+            .collect();
+        assert_eq!(
+            conditions,
+            [
+                Some(Condition::New),
+                Some(Condition::Used),
+                Some(Condition::ForParts),
+                Some(Condition::Refurbished),
+                Some(Condition::New),
+            ],
+            "{site}"
+        );
+
+        let formats: Vec<_> = page.items.iter().map(|item| item.buying.format).collect();
+        assert_eq!(
+            formats,
+            [
+                Some(BuyingFormat::BuyItNow),
+                Some(BuyingFormat::Auction),
+                Some(BuyingFormat::BestOffer),
+                None,
+                Some(BuyingFormat::BuyItNow),
+            ],
+            "{site}"
+        );
+
+        let costs: Vec<_> = page
+            .items
+            .iter()
+            .map(|item| item.shipping.as_ref().unwrap().cost.clone())
+            .collect();
+        assert_eq!(
+            costs[..4],
+            [
+                ShippingCost::Paid(Money::new(599, currency)),
+                ShippingCost::Free,
+                ShippingCost::LocalPickup,
+                ShippingCost::NotSpecified,
+            ],
+            "{site}"
+        );
+
+        let sections: Vec<_> = page.items.iter().map(|item| item.section).collect();
+        assert_eq!(
+            sections,
+            [
+                Section::Exact,
+                Section::Exact,
+                Section::Exact,
+                Section::FewerWords,
+                Section::International,
+            ],
+            "{site}"
+        );
+
+        let seller = first.seller.as_ref().unwrap();
+        assert_eq!(seller.feedback, Some(987), "{site}");
+        assert_eq!(seller.positive(), Some(99.5), "{site}");
+    }
+}
+
+#[test]
+fn keeps_foreign_currencies() {
+    let cases = [
+        (DE, Money::new(1500, "USD"), Money::new(2700, "USD")),
+        (CO_UK, Money::new(1500, "EUR"), Money::new(2700, "EUR")),
+        (IT, Money::new(1500, "USD"), Money::new(2700, "USD")),
+        (FR, Money::new(1500, "GBP"), Money::new(2700, "GBP")),
+    ];
+
+    for (html, price, total) in cases {
+        let page = parse_search_page(html).unwrap();
+        let imported = &page.items[4];
+
+        assert_eq!(imported.price.min, Some(price));
+        assert_eq!(imported.total(), Some(total));
+    }
+}
+
+#[test]
+fn extracts_item_ids_from_slugged_urls() {
+    let page = parse_search_page(DE).unwrap();
+    let ids: Vec<_> = page.items.iter().map(|item| item.id.as_str()).collect();
+
+    assert_eq!(
+        ids,
+        [
+            "123456789012",
+            "234567890123",
+            "345678901234",
+            "456789012345",
+            "567890123456",
+        ]
+    );
+    assert_eq!(
+        page.items[0].url,
+        "https://www.ebay.de/itm/Uberlebensset-15-in-1/123456789012"
+    );
+}
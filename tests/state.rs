@@ -0,0 +1,134 @@
+use std::{env, fs, path::PathBuf, process};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ebay2atom::{parse_search_page, state::StateStore, SearchItem};
+
+const CLASSIC: &str = include_str!("fixtures/classic.html");
+
+/// A fresh directory for the state files of `test`.
+fn directory(test: &str) -> PathBuf {
+    let directory = env::temp_dir().join(format!("ebay2atom-state-{test}-{}", process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+fn item() -> SearchItem {
+    parse_search_page(CLASSIC).unwrap().items.remove(0)
+}
+
+fn day(day: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, day, 12, 0, 0).unwrap()
+}
+
+#[test]
+fn keeps_first_seen_times_across_runs() {
+    let directory = directory("runs");
+    let path = directory.join("feed.tsv");
+    let item = item();
+
+    let mut store = StateStore::open(&path).unwrap();
+    assert!(store.get(&item.id).is_none());
+    store.observe(&item, day(1));
+    store.save(day(1)).unwrap();
+
+    let mut store = StateStore::open(&path).unwrap();
+    let state = store.observe(&item, day(2)).clone();
+    store.save(day(2)).unwrap();
+    assert_eq!(state.first_seen, day(1));
+    assert_eq!(state.updated, day(1));
+    assert_eq!(state.last_seen, day(2));
+
+    let store = StateStore::open(&path).unwrap();
+    assert_eq!(store.get(&item.id), Some(&state));
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn updates_changed_items() {
+    let mut store = StateStore::open(directory("changes").join("feed.tsv")).unwrap();
+    let mut item = item();
+    store.observe(&item, day(1));
+
+    item.time_left = Some("1h left".to_owned());
+    assert_eq!(store.observe(&item, day(2)).updated, day(1));
+
+    item.price.raw = "$29.00".to_owned();
+    let state = store.observe(&item, day(3));
+    assert_eq!(state.first_seen, day(1));
+    assert_eq!(state.updated, day(3));
+}
+
+#[test]
+fn forgets_items_after_30_days() {
+    let directory = directory("retention");
+    let path = directory.join("feed.tsv");
+    let page = parse_search_page(CLASSIC).unwrap();
+    let (old, recent) = (&page.items[0], &page.items[1]);
+
+    let mut store = StateStore::open(&path).unwrap();
+    store.observe(old, day(1));
+    store.observe(recent, day(2));
+    store.save(day(1) + Duration::days(30)).unwrap();
+    assert!(store.get(&old.id).is_some());
+
+    store.save(day(1) + Duration::days(31)).unwrap();
+    let store = StateStore::open(&path).unwrap();
+    assert!(store.get(&old.id).is_none());
+    assert!(store.get(&recent.id).is_some());
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn skips_malformed_lines() {
+    let directory = directory("malformed");
+    let path = directory.join("feed.tsv");
+    let valid = "123456789012\t2024-01-01T12:00:00+00:00\t2024-01-02T12:00:00+00:00\t2024-01-03T12:00:00+00:00\t00000000deadbeef";
+    let lines = [
+        "",
+        "garbage",
+        "234567890123\tyesterday\t2024-01-02T12:00:00+00:00\t2024-01-03T12:00:00+00:00\t0",
+        "345678901234\t2024-01-01T12:00:00+00:00\t2024-01-02T12:00:00+00:00\t2024-01-03T12:00:00+00:00\tnot hex",
+        "456789012345\t2024-01-01T12:00:00+00:00",
+        valid,
+    ];
+    fs::write(&path, lines.join("\n")).unwrap();
+
+    let store = StateStore::open(&path).unwrap();
+    fs::remove_dir_all(directory).unwrap();
+
+    let state = store.get("123456789012").unwrap();
+    assert_eq!(state.first_seen, day(1));
+    assert_eq!(state.updated, day(2));
+    assert_eq!(state.last_seen, day(3));
+    for number in ["garbage", "234567890123", "345678901234", "456789012345"] {
+        assert!(store.get(number).is_none(), "{number}");
+    }
+}
+
+#[test]
+fn writes_through_a_sibling_file() {
+    let directory = directory("temporary");
+    let item = item();
+
+    for name in ["feed.tsv", "feed.tmp"] {
+        fs::write(directory.join("feed.tmp"), "unrelated").unwrap();
+        let path = directory.join(name);
+
+        let mut store = StateStore::open(&path).unwrap();
+        store.observe(&item, day(1));
+        store.save(day(1)).unwrap();
+
+        let store = StateStore::open(&path).unwrap();
+        assert!(store.get(&item.id).is_some(), "{name}");
+        assert!(!directory.join(format!("{name}.tmp")).exists(), "{name}");
+        if name != "feed.tmp" {
+            assert_eq!(
+                fs::read_to_string(directory.join("feed.tmp")).unwrap(),
+                "unrelated"
+            );
+        }
+    }
+
+    fs::remove_dir_all(directory).unwrap();
+}
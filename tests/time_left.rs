@@ -0,0 +1,103 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use chrono::Duration;
+use ebay2atom::{time_left, Site};
+
+#[test]
+fn parses_time_left() {
+    let cases = [
+        (
+            Site::Com,
+            "2d 4h left",
+            Duration::days(2) + Duration::hours(4),
+        ),
+        (
+            Site::CoUk,
+            "3h 12m",
+            Duration::hours(3) + Duration::minutes(12),
+        ),
+        (
+            Site::Com,
+            "45m 10s left",
+            Duration::minutes(45) + Duration::seconds(10),
+        ),
+        (
+            Site::De,
+            "Noch 2T 4Std.",
+            Duration::days(2) + Duration::hours(4),
+        ),
+        (
+            Site::De,
+            "Noch 12 Min. 5 Sek.",
+            Duration::minutes(12) + Duration::seconds(5),
+        ),
+        (Site::It, "2g 4h", Duration::days(2) + Duration::hours(4)),
+        (
+            Site::Fr,
+            "2j 4h restants",
+            Duration::days(2) + Duration::hours(4),
+        ),
+    ];
+
+    for (site, text, duration) in cases {
+        let parsed = time_left::parse(text, site.vocabulary());
+        assert_eq!(parsed, Some(duration), "{site} {text}");
+    }
+}
+
+#[test]
+fn rejects_other_text() {
+    for text in [
+        "",
+        "Ending soon",
+        "3 bids",
+        "99999999999999999999d",
+        "99999999999d left",
+        "365d",
+    ] {
+        assert_eq!(
+            time_left::parse(text, Site::Com.vocabulary()),
+            None,
+            "{text}"
+        );
+    }
+}
+
+#[test]
+fn uses_the_site_units() {
+    let german = Site::De.vocabulary();
+    assert_eq!(time_left::parse("2d 4h", german), None);
+    assert_eq!(time_left::parse("2T 4Std.", Site::Com.vocabulary()), None);
+}
+
+#[test]
+fn caps_the_time_left() {
+    let longest = time_left::parse("364d 23h 59m 59s", Site::Com.vocabulary());
+    assert_eq!(
+        longest.map(|duration| duration.num_seconds()),
+        Some(time_left::LIMIT_SECONDS - 1)
+    );
+
+    let html = include_str!("fixtures/classic.html").replace("2d 4h left", "99999999999d left");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ebay2atom"))
+        .args(["--no-state", "--format", "ndjson"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(html.as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let records = String::from_utf8(output.stdout).unwrap();
+    assert!(records.contains("\"time_left\":\"99999999999d left\""));
+    assert!(!records.contains("\"ends\":\""));
+}
@@ -0,0 +1,144 @@
+use std::{
+    env, fs,
+    io::Write,
+    process::{self, Command, Stdio},
+};
+
+use chrono::DateTime;
+use serde_json::Value;
+
+/// Listing texts that are known to be troublesome in XML.
+const CORPUS: &[&str] = &[
+    "AT&T",
+    "AT&amp;T",
+    "&nbsp;&copy;&bogus;",
+    "&",
+    "&#",
+    "&#x;",
+    "<b>bold</b>",
+    "<![CDATA[ x ]]>",
+    "]]>",
+    "< 5 > 3",
+    "\"quoted\" 'single'",
+    "\u{0}\u{1}\u{8}\u{b}\u{c}\u{1f}",
+    "&#0;&#1;&#x1F;&#127;&#x85;",
+    "\u{7f}\u{80}\u{9f}",
+    "\u{fffe}\u{ffff}",
+    "tab\there\r\nnew line",
+    "emoji 🎮 and ünïcödé",
+    "</div></content></entry>",
+    "<?xml version=\"1.0\"?>",
+    "<!-- comment -->",
+];
+
+const PAGE: &str = r#"<!DOCTYPE html>
+<html><head><script>{"baseUrl":"https://www.ebay.com/sch/i.html?_nkw=FIELD"}</script></head>
+<body>
+<input name="_nkw" value="FIELD">
+<div class="srp-river"><ul class="srp-river-results">
+<li><div class="s-item__wrapper">
+  <a class="s-item__link" href="https://www.ebay.com/itm/123456789012"><div class="s-item__title"><span role="heading">FIELD</span></div></a>
+  <span class="SECONDARY_INFO">FIELD</span>
+  <span class="s-item__price">FIELD</span>
+  <span class="s-item__time-left">FIELD</span>
+  <span class="s-item__purchase-options">FIELD</span>
+  <span class="lvformat">FIELD</span>
+</div></li>
+</ul></div>
+</body></html>
+"#;
+
+/// The XML output formats.
+const FORMATS: &[&str] = &["atom", "rss"];
+
+fn run(html: &str, format: &str) -> String {
+    run_with(html, &["--no-state", "--format", format])
+}
+
+fn run_with(html: &str, args: &[&str]) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ebay2atom"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(html.as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn assert_well_formed(feed: &str, input: &str) {
+    if let Err(error) = roxmltree::Document::parse(feed) {
+        panic!("malformed feed for {input:?}: {error}\n{feed}");
+    }
+}
+
+#[test]
+fn fixture_is_well_formed() {
+    for format in FORMATS {
+        let feed = run(include_str!("fixtures/classic.html"), format);
+        assert_well_formed(&feed, "fixtures/classic.html");
+    }
+}
+
+#[test]
+fn corpus_is_well_formed() {
+    for format in FORMATS {
+        for text in CORPUS {
+            let feed = run(&PAGE.replace("FIELD", text), format);
+            assert_well_formed(&feed, text);
+        }
+    }
+}
+
+#[test]
+fn json_feed_is_valid() {
+    let state = env::temp_dir().join(format!("ebay2atom-json-feed-{}.tsv", process::id()));
+    let state = state.to_str().unwrap();
+    let html = include_str!("fixtures/classic.html");
+    let output = run_with(html, &["--state", state, "--format", "jsonfeed"]);
+    fs::remove_file(state).unwrap();
+
+    let feed: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(feed["version"], "https://jsonfeed.org/version/1.1");
+    assert_eq!(feed["title"], "cool gadget");
+
+    let items = feed["items"].as_array().unwrap();
+    let ids: Vec<_> = items
+        .iter()
+        .map(|item| item["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        ids,
+        [
+            "tag:ebay.com,2024:item:123456789012",
+            "tag:ebay.com,2024:item:234567890123",
+            "tag:ebay.com,2024:item:345678901234",
+        ]
+    );
+
+    for item in items {
+        for field in ["date_published", "date_modified"] {
+            let date = item[field].as_str().unwrap();
+            assert!(
+                DateTime::parse_from_rfc3339(date).is_ok(),
+                "{field}: {date}"
+            );
+        }
+    }
+
+    for text in CORPUS {
+        let output = run(&PAGE.replace("FIELD", text), "jsonfeed");
+        let feed: Value = serde_json::from_str(&output).unwrap();
+        assert!(feed["items"].is_array(), "{text}");
+    }
+}